@@ -0,0 +1,306 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::ron_file::{load_ron, load_ron_or_default, save_ron, RonFileError};
+
+pub const BINDINGS_FILE: &str = "bindings.ron";
+
+/// Stick deflection below which a stick counts as released.
+pub const GAMEPAD_STICK_DEADZONE: f32 = 0.3;
+
+/// Something the player can do, independent of the device doing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Aim,
+    Fire,
+    Bomb,
+    Pause,
+    Thrust,
+    TurnLeft,
+    TurnRight,
+    /// Turning and thrusting in one go with a stick in classic mode.
+    Steer,
+}
+
+impl Action {
+    pub const ALL: [Action; 8] = [
+        Action::Aim,
+        Action::Fire,
+        Action::Bomb,
+        Action::Pause,
+        Action::Thrust,
+        Action::TurnLeft,
+        Action::TurnRight,
+        Action::Steer,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Aim => "Aim",
+            Action::Fire => "Fire",
+            Action::Bomb => "Bomb",
+            Action::Pause => "Pause",
+            Action::Thrust => "Thrust",
+            Action::TurnLeft => "Turn Left",
+            Action::TurnRight => "Turn Right",
+            Action::Steer => "Steer",
+        }
+    }
+
+    /// Whether the action reads a stick rather than buttons.
+    pub fn is_analog(&self) -> bool {
+        matches!(self, Action::Aim | Action::Steer)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GamepadStick {
+    Left,
+    Right,
+}
+
+impl GamepadStick {
+    fn axes(&self) -> (GamepadAxisType, GamepadAxisType) {
+        match self {
+            GamepadStick::Left => (GamepadAxisType::LeftStickX, GamepadAxisType::LeftStickY),
+            GamepadStick::Right => (GamepadAxisType::RightStickX, GamepadAxisType::RightStickY),
+        }
+    }
+}
+
+/// An input bound to an action. `Cursor` and `Stick` only make sense for the
+/// analog actions, the others for the button actions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButtonType),
+    Cursor,
+    Stick(GamepadStick),
+}
+
+impl Binding {
+    pub fn is_gamepad(&self) -> bool {
+        matches!(self, Binding::Gamepad(_) | Binding::Stick(_))
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Binding::Key(key) => format!("{:?}", key),
+            Binding::Mouse(MouseButton::Left) => "Left Click".to_string(),
+            Binding::Mouse(MouseButton::Right) => "Right Click".to_string(),
+            Binding::Mouse(MouseButton::Middle) => "Middle Click".to_string(),
+            Binding::Mouse(button) => format!("Mouse {:?}", button),
+            Binding::Gamepad(button) => format!("Pad {:?}", button),
+            Binding::Cursor => "Mouse".to_string(),
+            Binding::Stick(GamepadStick::Left) => "Left Stick".to_string(),
+            Binding::Stick(GamepadStick::Right) => "Right Stick".to_string(),
+        }
+    }
+}
+
+/// Inputs bound to every action, missing actions fall back to the defaults.
+#[derive(Debug, Clone, Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputBindings {
+    pub aim: Vec<Binding>,
+    pub fire: Vec<Binding>,
+    pub bomb: Vec<Binding>,
+    pub pause: Vec<Binding>,
+    pub thrust: Vec<Binding>,
+    pub turn_left: Vec<Binding>,
+    pub turn_right: Vec<Binding>,
+    pub steer: Vec<Binding>,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self {
+            aim: vec![Binding::Cursor, Binding::Stick(GamepadStick::Right)],
+            fire: vec![
+                Binding::Mouse(MouseButton::Left),
+                Binding::Key(KeyCode::Space),
+                Binding::Gamepad(GamepadButtonType::RightTrigger2),
+                Binding::Gamepad(GamepadButtonType::RightTrigger),
+            ],
+            bomb: vec![
+                Binding::Mouse(MouseButton::Right),
+                Binding::Key(KeyCode::B),
+                Binding::Gamepad(GamepadButtonType::LeftTrigger2),
+                Binding::Gamepad(GamepadButtonType::LeftTrigger),
+            ],
+            pause: vec![
+                Binding::Key(KeyCode::Escape),
+                Binding::Gamepad(GamepadButtonType::Start),
+            ],
+            thrust: vec![
+                Binding::Key(KeyCode::W),
+                Binding::Key(KeyCode::Up),
+                Binding::Gamepad(GamepadButtonType::South),
+            ],
+            turn_left: vec![
+                Binding::Key(KeyCode::A),
+                Binding::Key(KeyCode::Left),
+                Binding::Gamepad(GamepadButtonType::DPadLeft),
+            ],
+            turn_right: vec![
+                Binding::Key(KeyCode::D),
+                Binding::Key(KeyCode::Right),
+                Binding::Gamepad(GamepadButtonType::DPadRight),
+            ],
+            steer: vec![Binding::Stick(GamepadStick::Left)],
+        }
+    }
+}
+
+impl InputBindings {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RonFileError> {
+        load_ron(path)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), RonFileError> {
+        save_ron(path, self)
+    }
+
+    pub fn get(&self, action: Action) -> &Vec<Binding> {
+        match action {
+            Action::Aim => &self.aim,
+            Action::Fire => &self.fire,
+            Action::Bomb => &self.bomb,
+            Action::Pause => &self.pause,
+            Action::Thrust => &self.thrust,
+            Action::TurnLeft => &self.turn_left,
+            Action::TurnRight => &self.turn_right,
+            Action::Steer => &self.steer,
+        }
+    }
+
+    fn get_mut(&mut self, action: Action) -> &mut Vec<Binding> {
+        match action {
+            Action::Aim => &mut self.aim,
+            Action::Fire => &mut self.fire,
+            Action::Bomb => &mut self.bomb,
+            Action::Pause => &mut self.pause,
+            Action::Thrust => &mut self.thrust,
+            Action::TurnLeft => &mut self.turn_left,
+            Action::TurnRight => &mut self.turn_right,
+            Action::Steer => &mut self.steer,
+        }
+    }
+
+    /// Binds `binding` to `action` in place of the bindings of the same kind
+    /// of device, keyboard and mouse or gamepad.
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        let bindings = self.get_mut(action);
+        bindings.retain(|other| other.is_gamepad() != binding.is_gamepad());
+        bindings.push(binding);
+    }
+
+    /// Bindings of `action` as shown in the settings.
+    pub fn label(&self, action: Action) -> String {
+        let bindings = self.get(action);
+        if bindings.is_empty() {
+            return "Unbound".to_string();
+        }
+
+        bindings
+            .iter()
+            .map(Binding::label)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Where the bindings are kept, in the user's config directory when there is
+/// one.
+pub fn bindings_path() -> PathBuf {
+    dirs::config_dir()
+        .map(|dir| dir.join("asteroids"))
+        .unwrap_or_default()
+        .join(BINDINGS_FILE)
+}
+
+/// Reads the devices through the `InputBindings`, for the systems that care
+/// about actions rather than keys and buttons.
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    bindings: Res<'w, InputBindings>,
+    keys: Res<'w, Input<KeyCode>>,
+    mouse_buttons: Res<'w, Input<MouseButton>>,
+    gamepads: Res<'w, Gamepads>,
+    gamepad_buttons: Res<'w, Input<GamepadButton>>,
+    axes: Res<'w, Axis<GamepadAxis>>,
+}
+
+impl<'w> ActionInput<'w> {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.bindings
+            .get(action)
+            .iter()
+            .any(|binding| match *binding {
+                Binding::Key(key) => self.keys.pressed(key),
+                Binding::Mouse(button) => self.mouse_buttons.pressed(button),
+                Binding::Gamepad(button_type) => self.gamepads.iter().any(|gamepad| {
+                    self.gamepad_buttons
+                        .pressed(GamepadButton::new(gamepad, button_type))
+                }),
+                Binding::Cursor | Binding::Stick(_) => false,
+            })
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.bindings
+            .get(action)
+            .iter()
+            .any(|binding| match *binding {
+                Binding::Key(key) => self.keys.just_pressed(key),
+                Binding::Mouse(button) => self.mouse_buttons.just_pressed(button),
+                Binding::Gamepad(button_type) => self.gamepads.iter().any(|gamepad| {
+                    self.gamepad_buttons
+                        .just_pressed(GamepadButton::new(gamepad, button_type))
+                }),
+                Binding::Cursor | Binding::Stick(_) => false,
+            })
+    }
+
+    /// Whether `action` follows the mouse cursor.
+    pub fn uses_cursor(&self, action: Action) -> bool {
+        self.bindings.get(action).contains(&Binding::Cursor)
+    }
+
+    /// Deflection of the first stick bound to `action` that is pushed past
+    /// the deadzone.
+    pub fn stick_for(&self, action: Action) -> Option<Vec2> {
+        self.bindings
+            .get(action)
+            .iter()
+            .find_map(|binding| match binding {
+                Binding::Stick(stick) => self.stick(*stick),
+                _ => None,
+            })
+    }
+
+    /// Deflection of `stick` on any gamepad, if pushed past the deadzone.
+    pub fn stick(&self, stick: GamepadStick) -> Option<Vec2> {
+        let (x, y) = stick.axes();
+
+        self.gamepads.iter().find_map(|gamepad| {
+            let value = Vec2::new(
+                self.axes.get(GamepadAxis::new(gamepad, x)).unwrap_or(0.0),
+                self.axes.get(GamepadAxis::new(gamepad, y)).unwrap_or(0.0),
+            );
+            (value.length() > GAMEPAD_STICK_DEADZONE).then_some(value)
+        })
+    }
+}
+
+/// Loads the `InputBindings` resource from `bindings_path`.
+pub struct ActionPlugin;
+
+impl Plugin for ActionPlugin {
+    fn build(&self, app: &mut App) {
+        let bindings: InputBindings = load_ron_or_default(&bindings_path(), "bindings");
+        app.insert_resource(bindings);
+    }
+}
@@ -0,0 +1,479 @@
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{f32::consts::TAU, time::Duration};
+
+use crate::{
+    config::GameConfig,
+    difficulty::{Wave, WaveConfig},
+    physics::{wrap_position, Position, PreviousPosition},
+    player::Player,
+    powerup::ActivePowerUps,
+    rng::GameRng,
+    ResetGame, SimulationSet, WINDOW_HEIGHT, WINDOW_MARGIN, WINDOW_WIDTH,
+};
+
+pub const ASTEROID_RADIUS: f32 = 50.0;
+pub const ASTEROID_MIN_FRAGMENTS: u32 = 2;
+pub const ASTEROID_MAX_FRAGMENTS: u32 = 3;
+pub const ASTEROID_FRAGMENT_SPEED_FACTOR: f32 = 1.5;
+pub const ASTEROID_MAX_SPIN: f32 = 1.5;
+pub const ASTEROID_CURVE_RATE: f32 = 0.4;
+pub const ASTEROID_HOMING_RATE: f32 = 0.8;
+pub const ASTEROID_CURVED_CHANCE: f32 = 0.25;
+pub const ASTEROID_HOMING_CHANCE: f32 = 0.25;
+pub const ASTEROID_SPAWN_MIN_DISTANCE: f32 = 200.0;
+const ASTEROID_SIDES: usize = 9;
+/// Spawn positions tried before giving up on `asteroid_spawn_min_distance`.
+const ASTEROID_SPAWN_ATTEMPTS: usize = 10;
+
+#[derive(Component)]
+pub struct Asteroid;
+
+/// Size tier of an asteroid, larger ones split into smaller ones when shot.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum AsteroidSize {
+    Large,
+    Medium,
+    Small,
+}
+
+impl AsteroidSize {
+    pub fn radius(&self, config: &GameConfig) -> f32 {
+        match self {
+            AsteroidSize::Large => config.asteroid_radius,
+            AsteroidSize::Medium => config.asteroid_radius * 0.6,
+            AsteroidSize::Small => config.asteroid_radius * 0.35,
+        }
+    }
+
+    /// Points for destroying an asteroid of this size.
+    pub fn score(&self) -> u32 {
+        match self {
+            AsteroidSize::Large => 1,
+            AsteroidSize::Medium => 2,
+            AsteroidSize::Small => 3,
+        }
+    }
+
+    /// Size of the fragments this asteroid splits into, if any.
+    pub fn smaller(&self) -> Option<AsteroidSize> {
+        match self {
+            AsteroidSize::Large => Some(AsteroidSize::Medium),
+            AsteroidSize::Medium => Some(AsteroidSize::Small),
+            AsteroidSize::Small => None,
+        }
+    }
+}
+
+#[derive(Component, Default)]
+pub struct Velocity(pub Vec2);
+
+/// Spin in radians per second, counterclockwise.
+#[derive(Component, Default)]
+pub struct AngularVelocity(pub f32);
+
+/// How an asteroid steers after it spawned.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub enum MovementPattern {
+    /// Flies in a straight line.
+    Drifting,
+    /// Turns its velocity by `turn_rate` radians per second.
+    Curved { turn_rate: f32 },
+    /// Turns towards the player by at most `turn_rate` radians per second.
+    Homing { turn_rate: f32 },
+}
+
+/// Asteroid still flying in from outside the screen, it is not wrapped
+/// around the screen edges and keeps a straight course until it is visible.
+#[derive(Component)]
+pub struct EnteringScreen;
+
+/// Where new asteroids appear.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum SpawnStrategy {
+    /// Within `WINDOW_MARGIN` of the four corners of the screen.
+    Corners,
+    /// Anywhere on the edges of the screen.
+    #[default]
+    Perimeter,
+    /// Anywhere just outside the edges of the screen, flying in.
+    OffScreen,
+    /// On the edges of the screen, picking a side by the relative weights.
+    WeightedSides {
+        top: f32,
+        bottom: f32,
+        left: f32,
+        right: f32,
+    },
+}
+
+impl SpawnStrategy {
+    /// Picks a spawn position for an asteroid of the given `radius`.
+    pub fn position(&self, radius: f32, rng: &mut impl Rng) -> Vec2 {
+        match *self {
+            SpawnStrategy::Corners => random_position_in_corner(rng),
+            SpawnStrategy::Perimeter => random_position_on_sides(
+                [WINDOW_WIDTH, WINDOW_WIDTH, WINDOW_HEIGHT, WINDOW_HEIGHT],
+                0.0,
+                rng,
+            ),
+            SpawnStrategy::OffScreen => random_position_on_sides(
+                [WINDOW_WIDTH, WINDOW_WIDTH, WINDOW_HEIGHT, WINDOW_HEIGHT],
+                radius,
+                rng,
+            ),
+            SpawnStrategy::WeightedSides {
+                top,
+                bottom,
+                left,
+                right,
+            } => random_position_on_sides([top, bottom, left, right], 0.0, rng),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct SpawnTimer {
+    pub timer: Timer,
+}
+
+impl SpawnTimer {
+    pub fn new(spawn_interval: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(spawn_interval, TimerMode::Once),
+        }
+    }
+}
+
+impl FromWorld for SpawnTimer {
+    fn from_world(world: &mut World) -> Self {
+        let config = world.resource::<GameConfig>();
+        Self::new(WaveConfig::for_wave(1, config).spawn_interval)
+    }
+}
+
+/// Spawns asteroids around the screen and moves them according to
+/// their `MovementPattern`, wrapping around the screen edges.
+pub struct AsteroidPlugin;
+
+impl Plugin for AsteroidPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpawnTimer>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    asteroid_spawn.in_set(SimulationSet::Spawn),
+                    asteroid_movement.in_set(SimulationSet::Movement),
+                ),
+            )
+            .add_systems(ResetGame, reset_asteroids);
+    }
+}
+
+fn random_position_in_corner(rng: &mut impl Rng) -> Vec2 {
+    let x = rng.gen_range(0.0..1.0);
+    let y = rng.gen_range(0.0..1.0);
+
+    let map_x = if x < 0.5 {
+        x * 2.0 * WINDOW_MARGIN
+    } else {
+        (x - 0.5) * 2.0 * WINDOW_MARGIN + WINDOW_WIDTH - WINDOW_MARGIN
+    } - WINDOW_WIDTH / 2.0;
+    let map_y = if y < 0.5 {
+        y * 2.0 * WINDOW_MARGIN
+    } else {
+        (y - 0.5) * 2.0 * WINDOW_MARGIN + WINDOW_HEIGHT - WINDOW_MARGIN
+    } - WINDOW_HEIGHT / 2.0;
+
+    Vec2::new(map_x, map_y)
+}
+
+/// Picks a side by the `[top, bottom, left, right]` weights and a point along
+/// it, pushed `outset` away from the screen.
+fn random_position_on_sides(weights: [f32; 4], outset: f32, rng: &mut impl Rng) -> Vec2 {
+    let half_width = WINDOW_WIDTH / 2.0;
+    let half_height = WINDOW_HEIGHT / 2.0;
+
+    let total: f32 = weights.iter().map(|weight| weight.max(0.0)).sum();
+    let mut roll = if total > 0.0 {
+        rng.gen_range(0.0..total)
+    } else {
+        0.0
+    };
+    let side = weights
+        .iter()
+        .position(|weight| {
+            roll -= weight.max(0.0);
+            roll < 0.0
+        })
+        .unwrap_or(0);
+
+    let x = rng.gen_range(-half_width..half_width);
+    let y = rng.gen_range(-half_height..half_height);
+
+    match side {
+        0 => Vec2::new(x, half_height + outset),
+        1 => Vec2::new(x, -half_height - outset),
+        2 => Vec2::new(-half_width - outset, y),
+        _ => Vec2::new(half_width + outset, y),
+    }
+}
+
+fn is_on_screen(position: Vec2) -> bool {
+    position.x.abs() <= WINDOW_WIDTH / 2.0 && position.y.abs() <= WINDOW_HEIGHT / 2.0
+}
+
+fn random_asteroid_speed(wave: &WaveConfig, rng: &mut impl Rng) -> f32 {
+    rng.gen_range(wave.min_speed..=wave.max_speed.max(wave.min_speed))
+}
+
+fn random_asteroid_size(wave: &WaveConfig, rng: &mut impl Rng) -> AsteroidSize {
+    wave.sizes
+        .choose(rng)
+        .copied()
+        .unwrap_or(AsteroidSize::Large)
+}
+
+fn random_spin(config: &GameConfig, rng: &mut impl Rng) -> f32 {
+    rng.gen_range(-config.asteroid_max_spin..=config.asteroid_max_spin)
+}
+
+fn random_movement_pattern(config: &GameConfig, rng: &mut impl Rng) -> MovementPattern {
+    let roll = rng.gen_range(0.0..1.0);
+
+    if roll < ASTEROID_CURVED_CHANCE {
+        let direction = if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+        MovementPattern::Curved {
+            turn_rate: direction * config.asteroid_curve_rate,
+        }
+    } else if roll < ASTEROID_CURVED_CHANCE + ASTEROID_HOMING_CHANCE {
+        MovementPattern::Homing {
+            turn_rate: config.asteroid_homing_rate,
+        }
+    } else {
+        MovementPattern::Drifting
+    }
+}
+
+fn asteroid_spawn(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut spawn_timer: ResMut<SpawnTimer>,
+    mut wave: ResMut<Wave>,
+    config: Res<GameConfig>,
+    mut rng: ResMut<GameRng>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    player_query: Query<&Position, With<Player>>,
+) {
+    if !wave.is_spawning() || wave.remaining == 0 {
+        return;
+    }
+
+    spawn_timer
+        .timer
+        .tick(Duration::from_secs_f32(time.delta_seconds()));
+
+    if spawn_timer.timer.finished() {
+        let target = player_query
+            .get_single()
+            .map(|position| position.0)
+            .unwrap_or_default();
+        let size = random_asteroid_size(&wave.settings, &mut *rng);
+        let radius = size.radius(&config);
+
+        // Keep away from the player, but do not stall the spawner when the
+        // strategy cannot satisfy the distance.
+        let mut position = config.asteroid_spawn_strategy.position(radius, &mut *rng);
+        for _ in 1..ASTEROID_SPAWN_ATTEMPTS {
+            if position.distance(target) >= config.asteroid_spawn_min_distance {
+                break;
+            }
+            position = config.asteroid_spawn_strategy.position(radius, &mut *rng);
+        }
+        let speed = random_asteroid_speed(&wave.settings, &mut *rng);
+
+        // Head roughly towards the player, any direction will do when the
+        // asteroid spawns right on top of it.
+        let direction = (target - position)
+            .try_normalize()
+            .unwrap_or_else(|| Vec2::from_angle(rng.gen_range(0.0..TAU)));
+        let spin = random_spin(&config, &mut *rng);
+        let pattern = random_movement_pattern(&config, &mut *rng);
+
+        let asteroid = spawn_asteroid(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            &config,
+            size,
+            position,
+            direction * speed,
+            spin,
+            pattern,
+        );
+
+        if !is_on_screen(position) {
+            commands.entity(asteroid).insert(EnteringScreen);
+        }
+
+        wave.remaining -= 1;
+        spawn_timer.timer.reset();
+    }
+}
+
+pub fn spawn_asteroid(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    config: &GameConfig,
+    size: AsteroidSize,
+    position: Vec2,
+    velocity: Vec2,
+    spin: f32,
+    pattern: MovementPattern,
+) -> Entity {
+    commands
+        .spawn(MaterialMesh2dBundle {
+            mesh: meshes
+                .add(shape::RegularPolygon::new(size.radius(config), ASTEROID_SIDES).into())
+                .into(),
+            material: materials.add(ColorMaterial::from(Color::PURPLE)),
+            transform: Transform::from_translation(position.extend(0.0)),
+            ..default()
+        })
+        .insert(Velocity(velocity))
+        .insert(AngularVelocity(spin))
+        .insert(pattern)
+        .insert(Position(position))
+        .insert(PreviousPosition(position))
+        .insert(size)
+        .insert(Asteroid)
+        .id()
+}
+
+/// Spawns the fragments of a destroyed asteroid, flying apart from where it
+/// was and faster than it.
+pub fn split_asteroid(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    config: &GameConfig,
+    rng: &mut impl Rng,
+    size: AsteroidSize,
+    position: Vec2,
+    velocity: Vec2,
+    pattern: MovementPattern,
+) {
+    let Some(fragment_size) = size.smaller() else {
+        return;
+    };
+
+    let count = rng.gen_range(ASTEROID_MIN_FRAGMENTS..=ASTEROID_MAX_FRAGMENTS);
+    let start_angle = rng.gen_range(0.0..TAU);
+    let speed = velocity.length();
+
+    for i in 0..count {
+        let direction = Vec2::from_angle(start_angle + TAU * i as f32 / count as f32);
+        let fragment_speed = speed * rng.gen_range(1.0..ASTEROID_FRAGMENT_SPEED_FACTOR);
+        let spin = random_spin(config, rng);
+
+        spawn_asteroid(
+            commands,
+            meshes,
+            materials,
+            config,
+            fragment_size,
+            position + direction * fragment_size.radius(config),
+            direction * fragment_speed,
+            spin,
+            pattern,
+        );
+    }
+}
+
+fn asteroid_movement(
+    time: Res<Time>,
+    mut commands: Commands,
+    player_query: Query<&Position, With<Player>>,
+    mut asteroid_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Position,
+            &mut PreviousPosition,
+            &mut Velocity,
+            &AngularVelocity,
+            &MovementPattern,
+            Has<EnteringScreen>,
+        ),
+        (With<Asteroid>, Without<Player>),
+    >,
+    power_ups: Res<ActivePowerUps>,
+    config: Res<GameConfig>,
+) {
+    let dt = time.delta_seconds() * power_ups.time_scale(&config);
+    let target = player_query.get_single().map(|position| position.0).ok();
+
+    for (
+        entity,
+        mut transform,
+        mut position,
+        mut previous,
+        mut velocity,
+        spin,
+        pattern,
+        entering,
+    ) in asteroid_query.iter_mut()
+    {
+        transform.rotate_z(spin.0 * dt);
+
+        if entering {
+            position.0 += velocity.0 * dt;
+
+            if is_on_screen(position.0) {
+                commands.entity(entity).remove::<EnteringScreen>();
+            }
+            continue;
+        }
+
+        match *pattern {
+            MovementPattern::Drifting => {}
+            MovementPattern::Curved { turn_rate } => {
+                velocity.0 = Vec2::from_angle(turn_rate * dt).rotate(velocity.0);
+            }
+            MovementPattern::Homing { turn_rate } => {
+                let desired = target.and_then(|target| (target - position.0).try_normalize());
+
+                if let Some(desired) = desired {
+                    let angle = velocity.0.angle_between(desired);
+                    if angle.is_finite() {
+                        let max_turn = turn_rate * dt;
+                        velocity.0 =
+                            Vec2::from_angle(angle.clamp(-max_turn, max_turn)).rotate(velocity.0);
+                    }
+                }
+            }
+        }
+
+        let moved = position.0 + velocity.0 * dt;
+        let wrapped = wrap_position(moved);
+
+        previous.0 += wrapped - moved;
+        position.0 = wrapped;
+    }
+}
+
+fn reset_asteroids(
+    mut commands: Commands,
+    asteroid_query: Query<Entity, With<Asteroid>>,
+    mut spawn_timer: ResMut<SpawnTimer>,
+    config: Res<GameConfig>,
+) {
+    for entity in asteroid_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    *spawn_timer = SpawnTimer::new(WaveConfig::for_wave(1, &config).spawn_interval);
+}
@@ -0,0 +1,200 @@
+//! Runs the game logic without a window for a fixed number of ticks and
+//! prints the outcome as JSON, driven by a simple bot or a replay file.
+//!
+//! ```console
+//! cargo run --bin simulate -- --seed 42 --ticks 6400
+//! ```
+
+use asteroids::{
+    asteroid::{Asteroid, Velocity},
+    cli::{arg_value, parse_arg},
+    config::GameConfig,
+    difficulty::{RunTimer, Wave},
+    events::{AsteroidDestroyed, PlayerHit, ShotFired},
+    input::PlayerInput,
+    physics::Position,
+    player::Death,
+    replay::{ReplayFile, ReplayPlayback},
+    rng::GameRng,
+    score::Score,
+    AppState, AsteroidsSimulationPlugin, SimulationSet,
+};
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use serde::Serialize;
+use std::env;
+
+const DEFAULT_TICKS: u32 = 64 * 60;
+const DEFAULT_FIRE_INTERVAL: u32 = 8;
+/// The bot sets off a bomb when an asteroid gets this close to the ship.
+const BOT_BOMB_DISTANCE: f32 = 100.0;
+
+/// Aims ahead of the asteroid closest to the ship and fires every
+/// `fire_interval` ticks, using a bomb when the asteroid gets too close.
+#[derive(Resource)]
+struct Bot {
+    fire_interval: u32,
+    cooldown: u32,
+}
+
+#[derive(Default, Resource)]
+struct SimulationStats {
+    ticks: u32,
+    asteroids_spawned: u32,
+    asteroids_destroyed: u32,
+    shots_fired: u32,
+    hits_taken: u32,
+}
+
+#[derive(Serialize)]
+struct SimulationReport {
+    seed: u64,
+    ticks: u32,
+    game_over: bool,
+    score: u32,
+    survival_time: f32,
+    wave: u32,
+    asteroids_spawned: u32,
+    asteroids_destroyed: u32,
+    asteroids_alive: u32,
+    shots_fired: u32,
+    hits_taken: u32,
+    cause_of_death: Option<String>,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let ticks = parse_arg(&args, "--ticks").unwrap_or(DEFAULT_TICKS);
+    let fire_interval = parse_arg(&args, "--fire-interval").unwrap_or(DEFAULT_FIRE_INTERVAL);
+    let replay = arg_value(&args, "--replay").map(|path| match ReplayFile::load(&path) {
+        Ok(replay) => replay,
+        Err(err) => panic!("Could not load replay {}: {}", path, err),
+    });
+    let seed = match &replay {
+        Some(replay) => replay.seed,
+        None => parse_arg(&args, "--seed").unwrap_or_else(|| GameRng::from_entropy().seed),
+    };
+
+    let mut app = App::new();
+
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+        .init_asset::<Mesh>()
+        .init_asset::<ColorMaterial>()
+        .insert_resource(GameRng::from_seed(seed))
+        .init_resource::<SimulationStats>()
+        .add_plugins(AsteroidsSimulationPlugin)
+        .add_systems(FixedUpdate, count_ticks.in_set(SimulationSet::Progression))
+        .add_systems(Last, (count_spawned_asteroids, count_events));
+
+    match replay {
+        Some(replay) => {
+            app.insert_resource(ReplayPlayback::new(replay));
+        }
+        None => {
+            app.insert_resource(Bot {
+                fire_interval,
+                cooldown: 0,
+            })
+            .add_systems(FixedUpdate, bot_input.in_set(SimulationSet::Input))
+            .add_systems(Update, start_game.run_if(in_state(AppState::MainMenu)));
+        }
+    }
+
+    app.finish();
+    app.cleanup();
+
+    // The first update runs the startup systems, after that every update
+    // advances the simulation by exactly one fixed tick.
+    app.update();
+    let timestep = app.world.resource::<Time<Fixed>>().timestep();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(timestep));
+
+    while app.world.resource::<SimulationStats>().ticks < ticks
+        && *app.world.resource::<State<AppState>>().get() != AppState::GameOver
+    {
+        app.update();
+    }
+
+    let asteroids_alive = app
+        .world
+        .query_filtered::<(), With<Asteroid>>()
+        .iter(&app.world)
+        .count() as u32;
+    let stats = app.world.resource::<SimulationStats>();
+
+    let report = SimulationReport {
+        seed,
+        ticks: stats.ticks,
+        game_over: *app.world.resource::<State<AppState>>().get() == AppState::GameOver,
+        score: app.world.resource::<Score>().value,
+        survival_time: app.world.resource::<RunTimer>().stopwatch.elapsed_secs(),
+        wave: app.world.resource::<Wave>().number,
+        asteroids_spawned: stats.asteroids_spawned,
+        asteroids_destroyed: stats.asteroids_destroyed,
+        asteroids_alive,
+        shots_fired: stats.shots_fired,
+        hits_taken: stats.hits_taken,
+        cause_of_death: app
+            .world
+            .resource::<Death>()
+            .cause
+            .map(|cause| cause.to_string()),
+    };
+
+    match serde_json::to_string_pretty(&report) {
+        Ok(json) => println!("{}", json),
+        Err(err) => panic!("Could not serialize the report: {}", err),
+    }
+}
+
+fn start_game(mut next_state: ResMut<NextState<AppState>>) {
+    next_state.set(AppState::InGame);
+}
+
+fn bot_input(
+    mut bot: ResMut<Bot>,
+    asteroid_query: Query<(&Position, &Velocity), With<Asteroid>>,
+    mut input: ResMut<PlayerInput>,
+    config: Res<GameConfig>,
+) {
+    // Lead the target by the time the bullet needs to reach where it is now.
+    let closest = asteroid_query
+        .iter()
+        .min_by(|(a, _), (b, _)| a.0.length_squared().total_cmp(&b.0.length_squared()));
+    let target = closest.map(|(position, velocity)| {
+        position.0 + velocity.0 * position.0.length() / config.bullet_speed
+    });
+
+    input.aim = target;
+    input.fire = false;
+    input.bomb = closest.is_some_and(|(position, _)| position.0.length() < BOT_BOMB_DISTANCE);
+
+    if bot.cooldown > 0 {
+        bot.cooldown -= 1;
+    } else if target.is_some() {
+        input.fire = true;
+        bot.cooldown = bot.fire_interval;
+    }
+}
+
+fn count_ticks(mut stats: ResMut<SimulationStats>) {
+    stats.ticks += 1;
+}
+
+fn count_spawned_asteroids(
+    asteroid_query: Query<(), Added<Asteroid>>,
+    mut stats: ResMut<SimulationStats>,
+) {
+    stats.asteroids_spawned += asteroid_query.iter().count() as u32;
+}
+
+fn count_events(
+    mut destroyed: EventReader<AsteroidDestroyed>,
+    mut fired: EventReader<ShotFired>,
+    mut hits: EventReader<PlayerHit>,
+    mut stats: ResMut<SimulationStats>,
+) {
+    stats.asteroids_destroyed += destroyed.read().count() as u32;
+    stats.shots_fired += fired.read().count() as u32;
+    stats.hits_taken += hits.read().count() as u32;
+}
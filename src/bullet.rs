@@ -0,0 +1,188 @@
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use std::time::Duration;
+
+use crate::{
+    asteroid::{split_asteroid, Asteroid, AsteroidSize, MovementPattern, Velocity},
+    config::GameConfig,
+    events::{AsteroidDestroyed, BulletMissed, DestroyCause},
+    physics::{Position, PreviousPosition},
+    powerup::maybe_drop_power_up,
+    rng::GameRng,
+    ResetGame, SimulationSet,
+};
+
+pub const BULLET_RADIUS: f32 = 3.0;
+pub const BULLET_SPEED: f32 = 600.0;
+pub const BULLET_LIFETIME: f32 = 1.0;
+pub const BULLET_COOLDOWN: f32 = 0.2;
+
+#[derive(Component)]
+pub struct Bullet {
+    pub velocity: Vec2,
+    pub lifetime: Timer,
+    /// Bullets fired together share the same shot.
+    pub shot: u32,
+}
+
+/// Time left until the ship can fire again.
+#[derive(Resource)]
+pub struct FireCooldown {
+    pub timer: Timer,
+}
+
+impl FireCooldown {
+    /// Creates a cooldown that is ready to fire.
+    pub fn new(cooldown: f32) -> Self {
+        let mut timer = Timer::from_seconds(cooldown, TimerMode::Once);
+        timer.set_elapsed(timer.duration());
+        Self { timer }
+    }
+}
+
+impl FromWorld for FireCooldown {
+    fn from_world(world: &mut World) -> Self {
+        Self::new(world.resource::<GameConfig>().bullet_cooldown)
+    }
+}
+
+/// Moves bullets, expires them and destroys the asteroids they hit.
+pub struct BulletPlugin;
+
+impl Plugin for BulletPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FireCooldown>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    bullet_movement.in_set(SimulationSet::Movement),
+                    bullet_collision.in_set(SimulationSet::Collision),
+                ),
+            )
+            .add_systems(ResetGame, reset_bullets);
+    }
+}
+
+pub fn spawn_bullet(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    config: &GameConfig,
+    position: Vec2,
+    velocity: Vec2,
+    shot: u32,
+) {
+    commands
+        .spawn(MaterialMesh2dBundle {
+            mesh: meshes
+                .add(shape::Circle::new(config.bullet_radius).into())
+                .into(),
+            material: materials.add(ColorMaterial::from(Color::WHITE)),
+            transform: Transform::from_translation(position.extend(1.0)),
+            ..default()
+        })
+        .insert(Position(position))
+        .insert(PreviousPosition(position))
+        .insert(Bullet {
+            velocity,
+            lifetime: Timer::from_seconds(config.bullet_lifetime, TimerMode::Once),
+            shot,
+        });
+}
+
+fn bullet_movement(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut bullet_query: Query<(Entity, &mut Position, &mut Bullet)>,
+    mut missed: EventWriter<BulletMissed>,
+) {
+    for (entity, mut position, mut bullet) in bullet_query.iter_mut() {
+        bullet
+            .lifetime
+            .tick(Duration::from_secs_f32(time.delta_seconds()));
+
+        if bullet.lifetime.finished() {
+            missed.send(BulletMissed { shot: bullet.shot });
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        position.0 += bullet.velocity * time.delta_seconds();
+    }
+}
+
+fn bullet_collision(
+    mut commands: Commands,
+    bullet_query: Query<(Entity, &Position, &Bullet)>,
+    asteroid_query: Query<
+        (
+            Entity,
+            &Position,
+            &Velocity,
+            &AsteroidSize,
+            &MovementPattern,
+        ),
+        With<Asteroid>,
+    >,
+    mut destroyed_events: EventWriter<AsteroidDestroyed>,
+    config: Res<GameConfig>,
+    mut rng: ResMut<GameRng>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let mut destroyed = Vec::new();
+
+    for (entity, bullet_position, bullet) in bullet_query.iter() {
+        let hit = asteroid_query
+            .iter()
+            .find(|(asteroid, position, _, size, _)| {
+                !destroyed.contains(asteroid)
+                    && position.0.distance(bullet_position.0)
+                        < size.radius(&config) + config.bullet_radius
+            });
+
+        if let Some((asteroid, position, velocity, size, pattern)) = hit {
+            destroyed.push(asteroid);
+            commands.entity(entity).despawn();
+            commands.entity(asteroid).despawn();
+            destroyed_events.send(AsteroidDestroyed {
+                entity: asteroid,
+                position: position.0,
+                size: *size,
+                cause: DestroyCause::Bullet { shot: bullet.shot },
+            });
+
+            split_asteroid(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                &config,
+                &mut *rng,
+                *size,
+                position.0,
+                velocity.0,
+                *pattern,
+            );
+            maybe_drop_power_up(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                &config,
+                &mut *rng,
+                position.0,
+            );
+        }
+    }
+}
+
+fn reset_bullets(
+    mut commands: Commands,
+    bullet_query: Query<Entity, With<Bullet>>,
+    mut cooldown: ResMut<FireCooldown>,
+    config: Res<GameConfig>,
+) {
+    for entity in bullet_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    *cooldown = FireCooldown::new(config.bullet_cooldown);
+}
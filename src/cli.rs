@@ -0,0 +1,20 @@
+use std::str::FromStr;
+
+/// Returns the value following the `name` flag in `args`, if the flag is there.
+pub fn arg_value(args: &[String], name: &str) -> Option<String> {
+    args.iter()
+        .position(|arg| arg == name)
+        .map(|index| match args.get(index + 1) {
+            Some(value) => value.clone(),
+            None => panic!("{} expects a value", name),
+        })
+}
+
+/// Like `arg_value` but parses the value, panicking with a readable message
+/// if it is invalid.
+pub fn parse_arg<T: FromStr>(args: &[String], name: &str) -> Option<T> {
+    arg_value(args, name).map(|value| match value.parse() {
+        Ok(value) => value,
+        Err(_) => panic!("Invalid value for {}: {}", name, value),
+    })
+}
@@ -0,0 +1,393 @@
+use bevy::{
+    asset::{
+        io::{file::FileAssetReader, Reader},
+        AssetLoader, AsyncReadExt, LoadContext,
+    },
+    ecs::system::SystemParam,
+    prelude::*,
+    utils::BoxedFuture,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::{
+    asteroid::{
+        SpawnStrategy, SpawnTimer, ASTEROID_CURVE_RATE, ASTEROID_HOMING_RATE, ASTEROID_MAX_SPIN,
+        ASTEROID_RADIUS, ASTEROID_SPAWN_MIN_DISTANCE,
+    },
+    bullet::{FireCooldown, BULLET_COOLDOWN, BULLET_LIFETIME, BULLET_RADIUS, BULLET_SPEED},
+    difficulty::{
+        default_waves, Wave, WaveConfig, DIFFICULTY_EXTRA_ASTEROIDS, DIFFICULTY_SPAWN_RATE_FACTOR,
+        DIFFICULTY_SPEED_FACTOR, WAVE_BREAK,
+    },
+    player::{
+        PLAYER_HEIGHT, PLAYER_INVULNERABILITY, PLAYER_LIVES, PLAYER_RESPAWN_CLEAR_RADIUS,
+        PLAYER_WIDTH, SHIP_DRAG, SHIP_MAX_SPEED, SHIP_THRUST, SHIP_TURN_SPEED,
+    },
+    powerup::{
+        POWER_UP_DROP_CHANCE, POWER_UP_DURATION, POWER_UP_LIFETIME, POWER_UP_MULTISHOT_SPREAD,
+        POWER_UP_RADIUS, POWER_UP_SLOW_TIME_FACTOR,
+    },
+    replay::ReplayPlayback,
+    ron_file::load_ron,
+    score::{
+        COMBO_HITS_PER_MULTIPLIER, COMBO_MAX_MULTIPLIER, COMBO_MULTI_KILL_BONUS, COMBO_TIMEOUT,
+    },
+    TICK_RATE,
+};
+
+pub const GAME_CONFIG_PATH: &str = "game.config.ron";
+
+/// Gameplay tuning values, missing fields fall back to the defaults.
+#[derive(Debug, Clone, Resource, Asset, TypePath, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GameConfig {
+    pub tick_rate: f64,
+    pub player_width: f32,
+    pub player_height: f32,
+    pub player_lives: u32,
+    /// Seconds the ship cannot be hit after respawning.
+    pub player_invulnerability: f32,
+    /// Asteroids this close to the center are cleared when the ship respawns.
+    pub player_respawn_clear_radius: f32,
+    pub ship_thrust: f32,
+    pub ship_turn_speed: f32,
+    pub ship_drag: f32,
+    pub ship_max_speed: f32,
+    pub bullet_radius: f32,
+    pub bullet_speed: f32,
+    pub bullet_lifetime: f32,
+    pub bullet_cooldown: f32,
+    pub asteroid_radius: f32,
+    pub asteroid_spawn_strategy: SpawnStrategy,
+    pub asteroid_spawn_min_distance: f32,
+    pub asteroid_max_spin: f32,
+    pub asteroid_curve_rate: f32,
+    pub asteroid_homing_rate: f32,
+    /// Seconds without a hit before the combo breaks.
+    pub combo_timeout: f32,
+    /// Hits needed to raise the score multiplier by one.
+    pub combo_hits_per_multiplier: u32,
+    pub combo_max_multiplier: u32,
+    /// Points for every asteroid after the first destroyed by the same shot.
+    pub combo_multi_kill_bonus: u32,
+    /// Chance of a destroyed asteroid dropping a power-up, from 0 to 1.
+    pub power_up_drop_chance: f64,
+    pub power_up_radius: f32,
+    /// Seconds a dropped power-up waits to be picked up.
+    pub power_up_lifetime: f32,
+    /// Seconds the effect of a picked up power-up lasts.
+    pub power_up_duration: f32,
+    pub power_up_slow_time_factor: f32,
+    /// Angle in radians between the bullets of a multishot.
+    pub power_up_multishot_spread: f32,
+    pub waves: Vec<WaveConfig>,
+    /// Seconds between two waves.
+    pub wave_break: f32,
+    /// Per wave past the end of `waves`: factor of the spawn interval, added
+    /// speed fraction and added asteroid count.
+    pub difficulty_spawn_rate_factor: f32,
+    pub difficulty_speed_factor: f32,
+    pub difficulty_extra_asteroids: u32,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            tick_rate: TICK_RATE,
+            player_width: PLAYER_WIDTH,
+            player_height: PLAYER_HEIGHT,
+            player_lives: PLAYER_LIVES,
+            player_invulnerability: PLAYER_INVULNERABILITY,
+            player_respawn_clear_radius: PLAYER_RESPAWN_CLEAR_RADIUS,
+            ship_thrust: SHIP_THRUST,
+            ship_turn_speed: SHIP_TURN_SPEED,
+            ship_drag: SHIP_DRAG,
+            ship_max_speed: SHIP_MAX_SPEED,
+            bullet_radius: BULLET_RADIUS,
+            bullet_speed: BULLET_SPEED,
+            bullet_lifetime: BULLET_LIFETIME,
+            bullet_cooldown: BULLET_COOLDOWN,
+            asteroid_radius: ASTEROID_RADIUS,
+            asteroid_spawn_strategy: SpawnStrategy::default(),
+            asteroid_spawn_min_distance: ASTEROID_SPAWN_MIN_DISTANCE,
+            asteroid_max_spin: ASTEROID_MAX_SPIN,
+            asteroid_curve_rate: ASTEROID_CURVE_RATE,
+            asteroid_homing_rate: ASTEROID_HOMING_RATE,
+            combo_timeout: COMBO_TIMEOUT,
+            combo_hits_per_multiplier: COMBO_HITS_PER_MULTIPLIER,
+            combo_max_multiplier: COMBO_MAX_MULTIPLIER,
+            combo_multi_kill_bonus: COMBO_MULTI_KILL_BONUS,
+            power_up_drop_chance: POWER_UP_DROP_CHANCE,
+            power_up_radius: POWER_UP_RADIUS,
+            power_up_lifetime: POWER_UP_LIFETIME,
+            power_up_duration: POWER_UP_DURATION,
+            power_up_slow_time_factor: POWER_UP_SLOW_TIME_FACTOR,
+            power_up_multishot_spread: POWER_UP_MULTISHOT_SPREAD,
+            waves: default_waves(),
+            wave_break: WAVE_BREAK,
+            difficulty_spawn_rate_factor: DIFFICULTY_SPAWN_RATE_FACTOR,
+            difficulty_speed_factor: DIFFICULTY_SPEED_FACTOR,
+            difficulty_extra_asteroids: DIFFICULTY_EXTRA_ASTEROIDS,
+        }
+    }
+}
+
+impl GameConfig {
+    /// Reads the config from the assets folder, bypassing the asset server so
+    /// the values are available while the app is being built.
+    pub fn load(path: &str) -> Self {
+        let path = FileAssetReader::get_base_path().join("assets").join(path);
+
+        let config: Self = match load_ron(&path) {
+            Ok(config) => config,
+            Err(err) => {
+                warn!(
+                    "Could not load {}, using the default config: {}",
+                    path.display(),
+                    err
+                );
+                return Self::default();
+            }
+        };
+
+        match config.validate() {
+            Ok(()) => config,
+            Err(err) => {
+                warn!(
+                    "Invalid config in {}, using the default config: {}",
+                    path.display(),
+                    err
+                );
+                Self::default()
+            }
+        }
+    }
+
+    /// Checks the values the game would panic on: a tick rate that is not
+    /// positive, negative durations and intervals, and negative rates.
+    pub fn validate(&self) -> Result<(), InvalidConfig> {
+        if !(self.tick_rate.is_finite() && self.tick_rate > 0.0) {
+            return Err(InvalidConfig {
+                field: "tick_rate".to_string(),
+                requirement: "positive",
+                value: self.tick_rate,
+            });
+        }
+
+        let non_negative = [
+            ("player_invulnerability", self.player_invulnerability),
+            ("bullet_lifetime", self.bullet_lifetime),
+            ("bullet_cooldown", self.bullet_cooldown),
+            ("asteroid_max_spin", self.asteroid_max_spin),
+            ("asteroid_curve_rate", self.asteroid_curve_rate),
+            ("asteroid_homing_rate", self.asteroid_homing_rate),
+            ("combo_timeout", self.combo_timeout),
+            ("power_up_lifetime", self.power_up_lifetime),
+            ("power_up_duration", self.power_up_duration),
+            ("wave_break", self.wave_break),
+            (
+                "difficulty_spawn_rate_factor",
+                self.difficulty_spawn_rate_factor,
+            ),
+        ]
+        .map(|(field, value)| (field.to_string(), value));
+        let spawn_intervals = self.waves.iter().enumerate().map(|(index, wave)| {
+            (
+                format!("waves[{}].spawn_interval", index),
+                wave.spawn_interval,
+            )
+        });
+
+        for (field, value) in non_negative.into_iter().chain(spawn_intervals) {
+            if !(value.is_finite() && value >= 0.0) {
+                return Err(InvalidConfig {
+                    field,
+                    requirement: "zero or more",
+                    value: value as f64,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A config value the game cannot run with.
+#[derive(Debug, Error)]
+#[error("{field} must be {requirement}, got {value}")]
+pub struct InvalidConfig {
+    pub field: String,
+    pub requirement: &'static str,
+    pub value: f64,
+}
+
+#[derive(Debug, Error)]
+pub enum GameConfigLoaderError {
+    #[error("could not read the config: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse the config: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+#[derive(Default)]
+pub struct GameConfigLoader;
+
+impl AssetLoader for GameConfigLoader {
+    type Asset = GameConfig;
+    type Settings = ();
+    type Error = GameConfigLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            Ok(ron::de::from_bytes(&bytes)?)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["config.ron"]
+    }
+}
+
+#[derive(Resource)]
+struct GameConfigHandle(Handle<GameConfig>);
+
+/// Loads the `GameConfig` resource from `path` and keeps it in sync with the
+/// file when hot reloading is enabled, except while a replay plays back with
+/// its own config.
+pub struct ConfigPlugin {
+    pub path: String,
+}
+
+impl Default for ConfigPlugin {
+    fn default() -> Self {
+        Self {
+            path: GAME_CONFIG_PATH.to_string(),
+        }
+    }
+}
+
+impl Plugin for ConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<GameConfig>()
+            .init_asset_loader::<GameConfigLoader>();
+
+        let handle = app.world.resource::<AssetServer>().load(&self.path);
+
+        let config = GameConfig::load(&self.path);
+
+        app.insert_resource(Time::<Fixed>::from_hz(config.tick_rate))
+            .insert_resource(config)
+            .insert_resource(GameConfigHandle(handle))
+            .add_systems(
+                Update,
+                reload_config.run_if(not(resource_exists::<ReplayPlayback>())),
+            );
+    }
+}
+
+/// The `GameConfig` together with the state of the run built from it, to
+/// switch configs in the middle of a run.
+#[derive(SystemParam)]
+pub struct LiveConfig<'w> {
+    config: ResMut<'w, GameConfig>,
+    spawn_timer: ResMut<'w, SpawnTimer>,
+    cooldown: ResMut<'w, FireCooldown>,
+    wave: ResMut<'w, Wave>,
+    time: ResMut<'w, Time<Fixed>>,
+}
+
+impl<'w> LiveConfig<'w> {
+    /// Switches to `new_config` and updates the wave settings, timers and
+    /// tick rate of the current run to match.
+    pub fn apply(&mut self, new_config: &GameConfig) {
+        *self.config = new_config.clone();
+        self.wave.settings = WaveConfig::for_wave(self.wave.number, &self.config);
+        self.spawn_timer
+            .timer
+            .set_duration(Duration::from_secs_f32(self.wave.settings.spawn_interval));
+        self.cooldown
+            .timer
+            .set_duration(Duration::from_secs_f32(self.config.bullet_cooldown));
+        self.time.set_timestep_hz(self.config.tick_rate);
+    }
+}
+
+fn reload_config(
+    mut events: EventReader<AssetEvent<GameConfig>>,
+    handle: Res<GameConfigHandle>,
+    configs: Res<Assets<GameConfig>>,
+    mut live: LiveConfig,
+) {
+    for event in events.read() {
+        if !event.is_modified(&handle.0) {
+            continue;
+        }
+
+        if let Some(new_config) = configs.get(&handle.0) {
+            if let Err(err) = new_config.validate() {
+                warn!(
+                    "Keeping the current game config, the reloaded one is invalid: {}",
+                    err
+                );
+                continue;
+            }
+
+            info!("Reloaded the game config");
+            live.apply(new_config);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config_is_valid() {
+        assert!(GameConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn tick_rate_must_be_positive() {
+        let config = GameConfig {
+            tick_rate: 0.0,
+            ..default()
+        };
+
+        let err = config.validate().unwrap_err();
+        assert_eq!(err.field, "tick_rate");
+    }
+
+    #[test]
+    fn durations_and_rates_must_not_be_negative() {
+        let config = GameConfig {
+            bullet_cooldown: -0.1,
+            ..default()
+        };
+        assert_eq!(config.validate().unwrap_err().field, "bullet_cooldown");
+
+        let config = GameConfig {
+            asteroid_max_spin: f32::NAN,
+            ..default()
+        };
+        assert_eq!(config.validate().unwrap_err().field, "asteroid_max_spin");
+    }
+
+    #[test]
+    fn wave_spawn_intervals_must_not_be_negative() {
+        let mut config = GameConfig::default();
+        config.waves[1].spawn_interval = -1.0;
+
+        let err = config.validate().unwrap_err();
+        assert_eq!(err.field, "waves[1].spawn_interval");
+    }
+}
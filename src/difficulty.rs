@@ -0,0 +1,276 @@
+use bevy::{prelude::*, time::Stopwatch};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::{
+    asteroid::{Asteroid, AsteroidSize, SpawnTimer},
+    config::GameConfig,
+    ResetGame, SimulationSet,
+};
+
+pub const WAVE_BREAK: f32 = 3.0;
+pub const DIFFICULTY_SPAWN_RATE_FACTOR: f32 = 0.9;
+pub const DIFFICULTY_SPEED_FACTOR: f32 = 0.1;
+pub const DIFFICULTY_EXTRA_ASTEROIDS: u32 = 2;
+
+#[derive(Default, Resource)]
+pub struct RunTimer {
+    pub stopwatch: Stopwatch,
+}
+
+/// Asteroids of a single wave.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct WaveConfig {
+    /// Number of asteroids spawned during the wave.
+    pub asteroids: u32,
+    pub min_speed: f32,
+    pub max_speed: f32,
+    /// Sizes to pick from for every spawned asteroid, repeat one to make it
+    /// more likely.
+    pub sizes: Vec<AsteroidSize>,
+    /// Seconds between two spawns.
+    pub spawn_interval: f32,
+}
+
+impl Default for WaveConfig {
+    fn default() -> Self {
+        Self {
+            asteroids: 3,
+            min_speed: 50.0,
+            max_speed: 80.0,
+            sizes: vec![AsteroidSize::Large],
+            spawn_interval: 3.0,
+        }
+    }
+}
+
+/// The built-in wave list, used when the config does not set `waves`.
+pub fn default_waves() -> Vec<WaveConfig> {
+    vec![
+        WaveConfig::default(),
+        WaveConfig {
+            asteroids: 4,
+            min_speed: 50.0,
+            max_speed: 90.0,
+            sizes: vec![AsteroidSize::Large, AsteroidSize::Medium],
+            spawn_interval: 2.5,
+        },
+        WaveConfig {
+            asteroids: 5,
+            min_speed: 60.0,
+            max_speed: 100.0,
+            sizes: vec![AsteroidSize::Large],
+            spawn_interval: 2.5,
+        },
+        WaveConfig {
+            asteroids: 6,
+            min_speed: 60.0,
+            max_speed: 110.0,
+            sizes: vec![AsteroidSize::Large, AsteroidSize::Medium],
+            spawn_interval: 2.0,
+        },
+        WaveConfig {
+            asteroids: 8,
+            min_speed: 70.0,
+            max_speed: 120.0,
+            sizes: vec![
+                AsteroidSize::Large,
+                AsteroidSize::Large,
+                AsteroidSize::Medium,
+            ],
+            spawn_interval: 2.0,
+        },
+    ]
+}
+
+impl WaveConfig {
+    /// Settings of wave `number`, counting from 1. Once the wave list is
+    /// exhausted the last wave is repeated with more, faster and more
+    /// frequent asteroids every time.
+    pub fn for_wave(number: u32, config: &GameConfig) -> Self {
+        let index = number.saturating_sub(1) as usize;
+
+        if let Some(wave) = config.waves.get(index) {
+            return wave.clone();
+        }
+
+        let mut wave = config.waves.last().cloned().unwrap_or_default();
+        let extra = (index + 1).saturating_sub(config.waves.len().max(1)) as u32;
+        let speed_multiplier = 1.0 + config.difficulty_speed_factor * extra as f32;
+
+        wave.asteroids += config.difficulty_extra_asteroids * extra;
+        wave.min_speed *= speed_multiplier;
+        wave.max_speed *= speed_multiplier;
+        wave.spawn_interval *= config.difficulty_spawn_rate_factor.powi(extra as i32);
+        wave
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum WavePhase {
+    /// Waiting for the wave to start, the banner is shown meanwhile.
+    Break(Timer),
+    /// Spawning the wave, it ends when all of its asteroids are destroyed.
+    Spawning,
+}
+
+/// The current wave, or the upcoming one during a break.
+#[derive(Resource)]
+pub struct Wave {
+    pub number: u32,
+    pub settings: WaveConfig,
+    /// Asteroids of the wave still to spawn.
+    pub remaining: u32,
+    pub phase: WavePhase,
+}
+
+impl Wave {
+    /// Wave `number` with a break before it starts.
+    pub fn new(number: u32, config: &GameConfig) -> Self {
+        let settings = WaveConfig::for_wave(number, config);
+
+        Self {
+            number,
+            remaining: settings.asteroids,
+            settings,
+            phase: WavePhase::Break(Timer::from_seconds(config.wave_break, TimerMode::Once)),
+        }
+    }
+
+    pub fn is_spawning(&self) -> bool {
+        matches!(self.phase, WavePhase::Spawning)
+    }
+}
+
+impl FromWorld for Wave {
+    fn from_world(world: &mut World) -> Self {
+        Self::new(1, world.resource::<GameConfig>())
+    }
+}
+
+/// Times the current run and sends the asteroids in waves, with a break
+/// between them.
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunTimer>()
+            .init_resource::<Wave>()
+            .add_systems(
+                FixedUpdate,
+                (run_timer, wave_progression)
+                    .chain()
+                    .in_set(SimulationSet::Progression),
+            )
+            .add_systems(ResetGame, reset_difficulty);
+    }
+}
+
+pub fn format_run_time(stopwatch: &Stopwatch) -> String {
+    let seconds = stopwatch.elapsed().as_secs();
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn run_timer(time: Res<Time>, mut run_timer: ResMut<RunTimer>) {
+    run_timer.stopwatch.tick(time.delta());
+}
+
+fn wave_progression(
+    time: Res<Time>,
+    mut wave: ResMut<Wave>,
+    mut spawn_timer: ResMut<SpawnTimer>,
+    asteroid_query: Query<(), With<Asteroid>>,
+    config: Res<GameConfig>,
+) {
+    let wave = &mut *wave;
+
+    match &mut wave.phase {
+        WavePhase::Break(timer) => {
+            timer.tick(time.delta());
+
+            if timer.finished() {
+                info!("Wave {} started", wave.number);
+
+                // The first asteroid of the wave spawns right away.
+                let interval = Duration::from_secs_f32(wave.settings.spawn_interval);
+                spawn_timer.timer.set_duration(interval);
+                spawn_timer.timer.set_elapsed(interval);
+                wave.phase = WavePhase::Spawning;
+            }
+        }
+        WavePhase::Spawning => {
+            if wave.remaining == 0 && asteroid_query.is_empty() {
+                info!("Wave {} cleared", wave.number);
+
+                *wave = Wave::new(wave.number + 1, &config);
+            }
+        }
+    }
+}
+
+fn reset_difficulty(
+    mut run_timer: ResMut<RunTimer>,
+    mut wave: ResMut<Wave>,
+    config: Res<GameConfig>,
+) {
+    *run_timer = RunTimer::default();
+    *wave = Wave::new(1, &config);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn waves_from_the_list_are_used_as_is() {
+        let config = GameConfig::default();
+
+        assert_eq!(WaveConfig::for_wave(1, &config), config.waves[0]);
+        assert_eq!(
+            WaveConfig::for_wave(config.waves.len() as u32, &config),
+            *config.waves.last().unwrap()
+        );
+    }
+
+    #[test]
+    fn waves_past_the_list_scale_the_last_one() {
+        let config = GameConfig::default();
+        let last = config.waves.last().unwrap().clone();
+
+        let wave = WaveConfig::for_wave(config.waves.len() as u32 + 2, &config);
+
+        assert_eq!(
+            wave.asteroids,
+            last.asteroids + 2 * config.difficulty_extra_asteroids
+        );
+        let speed_multiplier = 1.0 + 2.0 * config.difficulty_speed_factor;
+        assert_eq!(wave.min_speed, last.min_speed * speed_multiplier);
+        assert_eq!(wave.max_speed, last.max_speed * speed_multiplier);
+        assert_eq!(
+            wave.spawn_interval,
+            last.spawn_interval * config.difficulty_spawn_rate_factor.powi(2)
+        );
+        assert_eq!(wave.sizes, last.sizes);
+    }
+
+    #[test]
+    fn empty_wave_list_scales_the_default_wave() {
+        let config = GameConfig {
+            waves: Vec::new(),
+            ..default()
+        };
+
+        assert_eq!(WaveConfig::for_wave(1, &config), WaveConfig::default());
+        assert_eq!(
+            WaveConfig::for_wave(2, &config).asteroids,
+            WaveConfig::default().asteroids + config.difficulty_extra_asteroids
+        );
+    }
+
+    #[test]
+    fn wave_zero_is_the_first_wave() {
+        let config = GameConfig::default();
+
+        assert_eq!(WaveConfig::for_wave(0, &config), config.waves[0]);
+    }
+}
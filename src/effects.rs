@@ -0,0 +1,79 @@
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use std::f32::consts::TAU;
+
+use crate::{events::AsteroidDestroyed, ResetGame};
+
+const DEBRIS_COUNT: u32 = 8;
+const DEBRIS_RADIUS: f32 = 2.0;
+const DEBRIS_SPEED: f32 = 120.0;
+const DEBRIS_LIFETIME: f32 = 0.5;
+
+/// Piece of a destroyed asteroid flying apart and fading out, purely
+/// cosmetic.
+#[derive(Component)]
+pub struct Debris {
+    pub velocity: Vec2,
+    pub lifetime: Timer,
+}
+
+/// Visual effects reacting to the gameplay events, outside the simulation.
+pub struct EffectsPlugin;
+
+impl Plugin for EffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (spawn_debris, animate_debris))
+            .add_systems(ResetGame, reset_effects);
+    }
+}
+
+fn spawn_debris(
+    mut commands: Commands,
+    mut destroyed: EventReader<AsteroidDestroyed>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for event in destroyed.read() {
+        for i in 0..DEBRIS_COUNT {
+            let direction = Vec2::from_angle(TAU * i as f32 / DEBRIS_COUNT as f32);
+
+            commands
+                .spawn(MaterialMesh2dBundle {
+                    mesh: meshes.add(shape::Circle::new(DEBRIS_RADIUS).into()).into(),
+                    material: materials.add(ColorMaterial::from(Color::GRAY)),
+                    transform: Transform::from_translation(event.position.extend(0.2)),
+                    ..default()
+                })
+                .insert(Debris {
+                    velocity: direction * DEBRIS_SPEED,
+                    lifetime: Timer::from_seconds(DEBRIS_LIFETIME, TimerMode::Once),
+                });
+        }
+    }
+}
+
+fn animate_debris(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut debris_query: Query<(Entity, &mut Transform, &mut Debris, &Handle<ColorMaterial>)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (entity, mut transform, mut debris, material) in debris_query.iter_mut() {
+        debris.lifetime.tick(time.delta());
+
+        if debris.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation += (debris.velocity * time.delta_seconds()).extend(0.0);
+        if let Some(material) = materials.get_mut(material) {
+            material.color.set_a(debris.lifetime.percent_left());
+        }
+    }
+}
+
+fn reset_effects(mut commands: Commands, debris_query: Query<Entity, With<Debris>>) {
+    for entity in debris_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+use std::fmt;
+
+use crate::asteroid::{AsteroidSize, MovementPattern};
+
+/// What destroyed an asteroid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DestroyCause {
+    /// Hit by a bullet of `shot`.
+    Bullet {
+        shot: u32,
+    },
+    Bomb,
+    /// Absorbed by the shield of the ship.
+    Shield,
+    /// Cleared to make room for the respawning ship.
+    Respawn,
+}
+
+/// Sent for every asteroid removed from play. The entity is despawned by the
+/// time the event is read.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct AsteroidDestroyed {
+    pub entity: Entity,
+    pub position: Vec2,
+    pub size: AsteroidSize,
+    pub cause: DestroyCause,
+}
+
+/// Sent when the ship fires, once for all the bullets of a multishot.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ShotFired {
+    pub shot: u32,
+    pub position: Vec2,
+    pub direction: Vec2,
+}
+
+/// Sent when a bullet expires without hitting anything.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct BulletMissed {
+    pub shot: u32,
+}
+
+/// The asteroid that destroyed the last ship of a run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeathCause {
+    pub size: AsteroidSize,
+    pub pattern: MovementPattern,
+}
+
+impl fmt::Display for DeathCause {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let size = match self.size {
+            AsteroidSize::Large => "large",
+            AsteroidSize::Medium => "medium",
+            AsteroidSize::Small => "small",
+        };
+
+        match self.pattern {
+            MovementPattern::Drifting => write!(f, "{} asteroid", size),
+            MovementPattern::Curved { .. } => write!(f, "{} curving asteroid", size),
+            MovementPattern::Homing { .. } => write!(f, "{} homing asteroid", size),
+        }
+    }
+}
+
+/// Sent when an asteroid hits the ship.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PlayerHit {
+    pub asteroid: Entity,
+    pub position: Vec2,
+    /// The shield took the hit and no life was lost.
+    pub shielded: bool,
+    pub lives_left: u32,
+}
+
+/// Sent exactly once per run, when the last life is lost.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PlayerDied {
+    /// The asteroid that hit the ship, it stays in play.
+    pub asteroid: Entity,
+    pub position: Vec2,
+    pub cause: DeathCause,
+}
+
+/// Sent after `PlayerDied` once the tick is scored, with the final score of
+/// the run.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct GameOverEvent {
+    pub score: u32,
+    pub cause: DeathCause,
+}
+
+/// Registers the gameplay events. The core systems only send them, scoring,
+/// effects, sounds and stats react to them.
+pub struct GameEventsPlugin;
+
+impl Plugin for GameEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<AsteroidDestroyed>()
+            .add_event::<ShotFired>()
+            .add_event::<BulletMissed>()
+            .add_event::<PlayerHit>()
+            .add_event::<PlayerDied>()
+            .add_event::<GameOverEvent>();
+    }
+}
@@ -0,0 +1,152 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::ron_file::{load_ron, load_ron_or_default, save_ron, RonFileError};
+
+pub const HIGH_SCORE_FILE: &str = "high_scores.ron";
+pub const HIGH_SCORE_ENTRIES: usize = 10;
+pub const HIGH_SCORE_NAME_LENGTH: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighScoreEntry {
+    pub name: String,
+    pub score: u32,
+    pub wave: u32,
+}
+
+/// The best runs, highest score first.
+#[derive(Debug, Default, Clone, Resource, Serialize, Deserialize)]
+pub struct HighScores {
+    pub entries: Vec<HighScoreEntry>,
+}
+
+impl HighScores {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RonFileError> {
+        load_ron(path)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), RonFileError> {
+        save_ron(path, self)
+    }
+
+    /// Whether `score` makes it into the table.
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0
+            && (self.entries.len() < HIGH_SCORE_ENTRIES
+                || self.entries.iter().any(|entry| score > entry.score))
+    }
+
+    /// Adds `entry` below the entries with the same or higher score and drops
+    /// the ones that no longer fit.
+    pub fn insert(&mut self, entry: HighScoreEntry) {
+        let index = self
+            .entries
+            .iter()
+            .position(|other| entry.score > other.score)
+            .unwrap_or(self.entries.len());
+
+        self.entries.insert(index, entry);
+        self.entries.truncate(HIGH_SCORE_ENTRIES);
+    }
+}
+
+/// Where the high scores are kept, in the user's data directory when there
+/// is one.
+pub fn high_score_path() -> PathBuf {
+    dirs::data_dir()
+        .map(|dir| dir.join("asteroids"))
+        .unwrap_or_default()
+        .join(HIGH_SCORE_FILE)
+}
+
+/// Loads the `HighScores` resource from `high_score_path`.
+pub struct HighScorePlugin;
+
+impl Plugin for HighScorePlugin {
+    fn build(&self, app: &mut App) {
+        let high_scores: HighScores = load_ron_or_default(&high_score_path(), "high scores");
+        app.insert_resource(high_scores);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, score: u32) -> HighScoreEntry {
+        HighScoreEntry {
+            name: name.to_string(),
+            score,
+            wave: 1,
+        }
+    }
+
+    fn full_table() -> HighScores {
+        HighScores {
+            entries: (1..=HIGH_SCORE_ENTRIES as u32)
+                .rev()
+                .map(|score| entry("Full", score * 10))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn any_score_qualifies_for_a_table_with_room() {
+        let high_scores = HighScores {
+            entries: vec![entry("First", 100)],
+        };
+
+        assert!(high_scores.qualifies(1));
+        assert!(!high_scores.qualifies(0));
+    }
+
+    #[test]
+    fn full_table_needs_a_better_score_than_the_last_entry() {
+        let high_scores = full_table();
+
+        assert!(!high_scores.qualifies(10));
+        assert!(high_scores.qualifies(11));
+    }
+
+    #[test]
+    fn ties_go_below_the_existing_entries() {
+        let mut high_scores = HighScores {
+            entries: vec![entry("First", 50), entry("Second", 30)],
+        };
+
+        high_scores.insert(entry("Tie", 50));
+
+        let names: Vec<_> = high_scores
+            .entries
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect();
+        assert_eq!(names, ["First", "Tie", "Second"]);
+    }
+
+    #[test]
+    fn insert_keeps_the_best_entries() {
+        let mut high_scores = full_table();
+
+        high_scores.insert(entry("New", 55));
+
+        assert_eq!(high_scores.entries.len(), HIGH_SCORE_ENTRIES);
+        assert_eq!(high_scores.entries[5], entry("New", 55));
+        assert_eq!(high_scores.entries.last().unwrap().score, 20);
+    }
+
+    #[test]
+    fn saved_table_loads_back() {
+        let path = std::env::temp_dir()
+            .join(format!("asteroids-test-{}", std::process::id()))
+            .join(HIGH_SCORE_FILE);
+        let high_scores = full_table();
+
+        high_scores.save(&path).unwrap();
+        let loaded = HighScores::load(&path).unwrap();
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert_eq!(loaded.entries, high_scores.entries);
+    }
+}
@@ -0,0 +1,270 @@
+use bevy::prelude::*;
+
+use crate::{
+    config::GameConfig,
+    despawn_screen,
+    difficulty::{format_run_time, RunTimer, Wave},
+    player::Lives,
+    powerup::ActivePowerUps,
+    score::{Combo, Score},
+    AppState,
+};
+
+const HUD_FONT_SIZE: f32 = 24.0;
+const WAVE_BANNER_FONT_SIZE: f32 = 64.0;
+
+#[derive(Component)]
+pub struct Hud;
+
+#[derive(Component)]
+struct ScoreText;
+
+#[derive(Component)]
+struct ComboText;
+
+#[derive(Component)]
+struct LivesText;
+
+#[derive(Component)]
+struct WaveText;
+
+#[derive(Component)]
+struct PowerUpText;
+
+#[derive(Component)]
+struct WaveBanner;
+
+#[derive(Component)]
+struct RunTimeText;
+
+/// Shows the score, combo, lives, wave, run time and active power-ups while
+/// playing, and a banner announcing each wave during the break before it.
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnTransition {
+                from: AppState::MainMenu,
+                to: AppState::InGame,
+            },
+            spawn_hud,
+        )
+        .add_systems(OnEnter(AppState::MainMenu), despawn_screen::<Hud>)
+        .add_systems(
+            Update,
+            (
+                update_score_text,
+                update_combo_text,
+                update_lives_text,
+                update_wave_text,
+                update_power_up_text,
+                update_wave_banner,
+                update_run_time_text,
+            ),
+        );
+    }
+}
+
+fn hud_text(text: impl Into<String>) -> TextBundle {
+    TextBundle::from_section(
+        text,
+        TextStyle {
+            font_size: HUD_FONT_SIZE,
+            color: Color::WHITE,
+            ..default()
+        },
+    )
+}
+
+fn spawn_hud(
+    mut commands: Commands,
+    score: Res<Score>,
+    lives: Res<Lives>,
+    wave: Res<Wave>,
+    run_timer: Res<RunTimer>,
+) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::SpaceBetween,
+                padding: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(Hud)
+        .with_children(|parent| {
+            parent
+                .spawn(hud_text(format!("Score: {}", score.value)))
+                .insert(ScoreText);
+            parent.spawn(hud_text("")).insert(ComboText);
+            parent
+                .spawn(hud_text(format!("Lives: {}", lives.value)))
+                .insert(LivesText);
+            parent
+                .spawn(hud_text(format!("Wave: {}", wave.number)))
+                .insert(WaveText);
+            parent
+                .spawn(hud_text(format_run_time(&run_timer.stopwatch)))
+                .insert(RunTimeText);
+        });
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .insert(Hud)
+        .with_children(|parent| {
+            parent
+                .spawn(TextBundle::from_section(
+                    format!("Wave {}", wave.number),
+                    TextStyle {
+                        font_size: WAVE_BANNER_FONT_SIZE,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ))
+                .insert(WaveBanner);
+        });
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(0.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(Hud)
+        .with_children(|parent| {
+            parent.spawn(hud_text("")).insert(PowerUpText);
+        });
+}
+
+/// Lists the active power-ups with their remaining seconds and the bombs
+/// held.
+fn power_up_label(power_ups: &ActivePowerUps) -> String {
+    let bombs = (power_ups.bombs > 0).then(|| format!("Bombs {}", power_ups.bombs));
+
+    [
+        ("Shield", &power_ups.shield),
+        ("Multishot", &power_ups.multishot),
+        ("Slow Time", &power_ups.slow_time),
+    ]
+    .iter()
+    .filter_map(|(name, timer)| {
+        timer
+            .as_ref()
+            .map(|timer| format!("{} {:.0}s", name, timer.remaining_secs().ceil()))
+    })
+    .chain(bombs)
+    .collect::<Vec<_>>()
+    .join("  ")
+}
+
+fn update_score_text(score: Res<Score>, mut text_query: Query<&mut Text, With<ScoreText>>) {
+    if !score.is_changed() {
+        return;
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = format!("Score: {}", score.value);
+    }
+}
+
+fn combo_label(combo: &Combo, config: &GameConfig) -> String {
+    match combo.multiplier(config) {
+        1 => String::new(),
+        multiplier => format!("Combo x{}", multiplier),
+    }
+}
+
+fn update_combo_text(
+    combo: Res<Combo>,
+    config: Res<GameConfig>,
+    mut text_query: Query<&mut Text, With<ComboText>>,
+) {
+    if !combo.is_changed() {
+        return;
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = combo_label(&combo, &config);
+    }
+}
+
+fn update_lives_text(lives: Res<Lives>, mut text_query: Query<&mut Text, With<LivesText>>) {
+    if !lives.is_changed() {
+        return;
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = format!("Lives: {}", lives.value);
+    }
+}
+
+fn update_power_up_text(
+    power_ups: Res<ActivePowerUps>,
+    mut text_query: Query<&mut Text, With<PowerUpText>>,
+) {
+    if !power_ups.is_changed() {
+        return;
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = power_up_label(&power_ups);
+    }
+}
+
+fn update_wave_text(wave: Res<Wave>, mut text_query: Query<&mut Text, With<WaveText>>) {
+    if !wave.is_changed() {
+        return;
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = format!("Wave: {}", wave.number);
+    }
+}
+
+fn update_wave_banner(
+    wave: Res<Wave>,
+    mut banner_query: Query<(&mut Text, &mut Visibility), With<WaveBanner>>,
+) {
+    if !wave.is_changed() {
+        return;
+    }
+
+    for (mut text, mut visibility) in banner_query.iter_mut() {
+        text.sections[0].value = format!("Wave {}", wave.number);
+        *visibility = if wave.is_spawning() {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+    }
+}
+
+fn update_run_time_text(
+    run_timer: Res<RunTimer>,
+    mut text_query: Query<&mut Text, With<RunTimeText>>,
+) {
+    if !run_timer.is_changed() {
+        return;
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = format_run_time(&run_timer.stopwatch);
+    }
+}
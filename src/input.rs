@@ -0,0 +1,199 @@
+use bevy::{
+    input::InputSystem,
+    prelude::*,
+    window::{CursorMoved, PrimaryWindow},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    action::{Action, ActionInput, GAMEPAD_STICK_DEADZONE},
+    physics::Position,
+    player::{ControlMode, Player},
+    replay::ReplayPlayback,
+    AppState, ResetGame, SimulationSet,
+};
+
+/// Distance from the ship of the aim point set by a gamepad stick.
+const GAMEPAD_AIM_DISTANCE: f32 = 150.0;
+const RETICLE_SIZE: f32 = 16.0;
+const RETICLE_THICKNESS: f32 = 2.0;
+
+/// Player input for the current fixed tick.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Resource, Serialize, Deserialize)]
+pub struct PlayerInput {
+    /// Aim position in world coordinates.
+    pub aim: Option<Vec2>,
+    pub fire: bool,
+    /// Turn direction of the ship in classic mode, positive turns left.
+    #[serde(default)]
+    pub turn: f32,
+    #[serde(default)]
+    pub thrust: bool,
+    #[serde(default)]
+    pub bomb: bool,
+}
+
+/// Live input gathered every frame until the next fixed tick consumes it.
+#[derive(Default, Resource)]
+struct PendingInput(PlayerInput);
+
+/// Provides the `PlayerInput` resource, some other plugin has to fill it.
+pub struct PlayerInputPlugin;
+
+impl Plugin for PlayerInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PlayerInput>()
+            .add_systems(ResetGame, reset_input);
+    }
+}
+
+/// Crosshair drawn at the aim point.
+#[derive(Component)]
+pub struct Reticle;
+
+/// Reads the actions bound to the mouse, keyboard and gamepads into
+/// `PlayerInput`, unless a replay is playing. In classic mode the steer stick
+/// turns and pushes forward to thrust.
+pub struct MouseInputPlugin;
+
+impl Plugin for MouseInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingInput>()
+            .add_systems(Startup, spawn_reticle)
+            // Clicks in the menus must not turn into shots once the game
+            // starts or resumes.
+            .add_systems(
+                PreUpdate,
+                read_live_input
+                    .after(InputSystem)
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(Update, update_reticle)
+            .add_systems(
+                FixedUpdate,
+                apply_live_input
+                    .in_set(SimulationSet::Input)
+                    .run_if(not(resource_exists::<ReplayPlayback>())),
+            )
+            .add_systems(ResetGame, reset_pending_input);
+    }
+}
+
+fn read_live_input(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    ship_query: Query<&Position, With<Player>>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    actions: ActionInput,
+    mut pending: ResMut<PendingInput>,
+    mut stick_aiming: Local<bool>,
+) {
+    // The aim stays where the stick left it until the mouse moves again.
+    if cursor_moved.read().count() > 0 {
+        *stick_aiming = false;
+    }
+
+    if let Some(direction) = actions.stick_for(Action::Aim) {
+        let ship = ship_query
+            .get_single()
+            .map(|ship| ship.0)
+            .unwrap_or_default();
+        pending.0.aim = Some(ship + direction.normalize() * GAMEPAD_AIM_DISTANCE);
+        *stick_aiming = true;
+    }
+
+    if !*stick_aiming && actions.uses_cursor(Action::Aim) {
+        let (camera, camera_transform) = camera_query.single();
+        let aim = window_query
+            .single()
+            .cursor_position()
+            .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor));
+
+        if let Some(aim) = aim {
+            pending.0.aim = Some(aim);
+        }
+    }
+
+    if actions.just_pressed(Action::Fire) {
+        pending.0.fire = true;
+    }
+    if actions.just_pressed(Action::Bomb) {
+        pending.0.bomb = true;
+    }
+
+    let mut turn = 0.0;
+    if actions.pressed(Action::TurnLeft) {
+        turn += 1.0;
+    }
+    if actions.pressed(Action::TurnRight) {
+        turn -= 1.0;
+    }
+
+    let mut thrust = actions.pressed(Action::Thrust);
+    if let Some(direction) = actions.stick_for(Action::Steer) {
+        turn -= direction.x;
+        thrust |= direction.y > GAMEPAD_STICK_DEADZONE;
+    }
+
+    pending.0.turn = turn.clamp(-1.0, 1.0);
+    pending.0.thrust = thrust;
+}
+
+fn apply_live_input(mut pending: ResMut<PendingInput>, mut input: ResMut<PlayerInput>) {
+    *input = pending.0;
+    pending.0.fire = false;
+    pending.0.bomb = false;
+}
+
+fn spawn_reticle(mut commands: Commands) {
+    commands
+        .spawn(SpatialBundle {
+            visibility: Visibility::Hidden,
+            ..default()
+        })
+        .insert(Reticle)
+        .with_children(|parent| {
+            for size in [
+                Vec2::new(RETICLE_SIZE, RETICLE_THICKNESS),
+                Vec2::new(RETICLE_THICKNESS, RETICLE_SIZE),
+            ] {
+                parent.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::rgba(1.0, 1.0, 1.0, 0.6),
+                        custom_size: Some(size),
+                        ..default()
+                    },
+                    ..default()
+                });
+            }
+        });
+}
+
+/// Shows the reticle at the aim point while aiming controls the ship.
+fn update_reticle(
+    input: Res<PlayerInput>,
+    control_mode: Res<ControlMode>,
+    state: Res<State<AppState>>,
+    mut reticle_query: Query<(&mut Transform, &mut Visibility), With<Reticle>>,
+) {
+    for (mut transform, mut visibility) in reticle_query.iter_mut() {
+        match input.aim {
+            Some(aim)
+                if *control_mode == ControlMode::PointAndClick
+                    && *state.get() == AppState::InGame =>
+            {
+                transform.translation = aim.extend(2.0);
+                *visibility = Visibility::Inherited;
+            }
+            _ => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+fn reset_input(mut input: ResMut<PlayerInput>) {
+    *input = PlayerInput::default();
+}
+
+fn reset_pending_input(mut pending: ResMut<PendingInput>) {
+    *pending = PendingInput::default();
+}
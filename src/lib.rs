@@ -0,0 +1,192 @@
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
+use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+
+pub mod action;
+pub mod asteroid;
+pub mod bullet;
+pub mod cli;
+pub mod config;
+pub mod difficulty;
+pub mod effects;
+pub mod events;
+pub mod highscore;
+pub mod hud;
+pub mod input;
+pub mod menu;
+pub mod physics;
+pub mod player;
+pub mod powerup;
+pub mod replay;
+pub mod rng;
+pub mod ron_file;
+pub mod score;
+pub mod sound;
+pub mod view;
+
+use action::ActionPlugin;
+use asteroid::AsteroidPlugin;
+use bullet::BulletPlugin;
+use config::ConfigPlugin;
+use difficulty::DifficultyPlugin;
+use effects::EffectsPlugin;
+use events::{GameEventsPlugin, GameOverEvent};
+use highscore::HighScorePlugin;
+use hud::HudPlugin;
+use input::{MouseInputPlugin, PlayerInputPlugin};
+use menu::MenuPlugin;
+use physics::PhysicsPlugin;
+use player::PlayerPlugin;
+use powerup::PowerUpPlugin;
+use replay::{ReplayPlugin, ReplayRecorderPlugin};
+use rng::RngPlugin;
+use score::ScorePlugin;
+use sound::SoundPlugin;
+use view::ViewPlugin;
+
+pub const WINDOW_WIDTH: f32 = 800.0;
+pub const WINDOW_HEIGHT: f32 = 600.0;
+pub const WINDOW_MARGIN: f32 = 50.0;
+
+pub const TICK_RATE: f64 = 64.0;
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
+pub enum AppState {
+    #[default]
+    MainMenu,
+    Settings,
+    Controls,
+    HighScores,
+    InGame,
+    Paused,
+    GameOver,
+}
+
+/// Stages of a `FixedUpdate` tick, run in this order while in game so the
+/// simulation is deterministic.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SimulationSet {
+    Prepare,
+    Input,
+    Player,
+    Spawn,
+    Movement,
+    /// Bullets hitting asteroids.
+    Collision,
+    /// Asteroids hitting the ship, once the ones shot this tick are gone.
+    ShipCollision,
+    Progression,
+    /// Reacts to the gameplay events sent earlier in the tick.
+    Events,
+}
+
+/// Schedule run whenever a new run starts, plugins add their cleanup systems to it.
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ResetGame;
+
+/// Game logic only, without any window, rendering or live input, so it can
+/// run with `MinimalPlugins`. Something has to fill `PlayerInput`.
+pub struct AsteroidsSimulationPlugin;
+
+impl Plugin for AsteroidsSimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<AppState>()
+            .init_schedule(ResetGame)
+            .configure_sets(
+                FixedUpdate,
+                (
+                    SimulationSet::Prepare,
+                    SimulationSet::Input,
+                    SimulationSet::Player,
+                    SimulationSet::Spawn,
+                    SimulationSet::Movement,
+                    SimulationSet::Collision,
+                    SimulationSet::ShipCollision,
+                    SimulationSet::Progression,
+                    SimulationSet::Events,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_plugins((
+                ConfigPlugin::default(),
+                GameEventsPlugin,
+                RngPlugin,
+                PhysicsPlugin,
+                PlayerInputPlugin,
+                ReplayPlugin,
+                PlayerPlugin,
+                AsteroidPlugin,
+                BulletPlugin,
+                PowerUpPlugin,
+                ScorePlugin,
+                DifficultyPlugin,
+            ))
+            // Bevy does not sync on its own, despawns queued by one set stay
+            // visible to the next until the commands are applied.
+            .add_systems(
+                FixedUpdate,
+                (
+                    apply_deferred
+                        .after(SimulationSet::Collision)
+                        .before(SimulationSet::ShipCollision),
+                    apply_deferred
+                        .after(SimulationSet::ShipCollision)
+                        .before(SimulationSet::Progression),
+                ),
+            )
+            .add_systems(Update, game_over)
+            .add_systems(OnEnter(AppState::MainMenu), reset_game)
+            .add_systems(
+                OnTransition {
+                    from: AppState::GameOver,
+                    to: AppState::InGame,
+                },
+                reset_game,
+            );
+    }
+}
+
+/// The whole game: the simulation together with live input, menus, HUD,
+/// effects, sounds and high scores.
+pub struct AsteroidsGamePlugin;
+
+impl Plugin for AsteroidsGamePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            AsteroidsSimulationPlugin,
+            ViewPlugin,
+            ActionPlugin,
+            MouseInputPlugin,
+            ReplayRecorderPlugin,
+            HighScorePlugin,
+            HudPlugin,
+            EffectsPlugin,
+            SoundPlugin,
+            MenuPlugin,
+        ));
+    }
+}
+
+fn game_over(mut events: EventReader<GameOverEvent>, mut next_state: ResMut<NextState<AppState>>) {
+    for event in events.read() {
+        info!(
+            "Game Over! Score: {}, destroyed by a {}",
+            event.score, event.cause
+        );
+        next_state.set(AppState::GameOver);
+    }
+}
+
+fn reset_game(world: &mut World) {
+    world.run_schedule(ResetGame);
+}
+
+pub(crate) fn despawn_screen<T: Component>(
+    mut commands: Commands,
+    screen_query: Query<Entity, With<T>>,
+) {
+    for entity in screen_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
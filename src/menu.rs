@@ -0,0 +1,658 @@
+use bevy::{
+    app::AppExit,
+    prelude::*,
+    window::{PresentMode, PrimaryWindow},
+};
+
+use crate::{
+    action::{bindings_path, Action, ActionInput, Binding, GamepadStick, InputBindings},
+    despawn_screen,
+    difficulty::{format_run_time, RunTimer, Wave},
+    highscore::{high_score_path, HighScoreEntry, HighScores, HIGH_SCORE_NAME_LENGTH},
+    player::{ControlMode, Death},
+    replay::ReplayPlayback,
+    rng::GameRng,
+    score::Score,
+    AppState,
+};
+
+const BUTTON_NORMAL_COLOR: Color = Color::rgb(0.15, 0.15, 0.15);
+const BUTTON_HOVERED_COLOR: Color = Color::rgb(0.25, 0.25, 0.25);
+const BUTTON_PRESSED_COLOR: Color = Color::rgb(0.35, 0.75, 0.35);
+
+#[derive(Component)]
+struct MainMenuScreen;
+
+#[derive(Component)]
+struct SettingsScreen;
+
+#[derive(Component)]
+struct ControlsScreen;
+
+#[derive(Component)]
+struct PauseScreen;
+
+#[derive(Component)]
+struct GameOverScreen;
+
+#[derive(Component)]
+struct HighScoreScreen;
+
+#[derive(Component)]
+struct NameEntryText;
+
+/// Name being typed for a new high score on the game over screen.
+#[derive(Default, Resource)]
+struct NameEntry {
+    name: String,
+}
+
+/// Text of a controls button that shows the bindings of `0`.
+#[derive(Component)]
+struct BindingLabel(Action);
+
+/// Action waiting for the next key or button press to be bound to it.
+#[derive(Resource)]
+struct Rebinding {
+    action: Action,
+}
+
+/// Text of a settings button that shows the current value.
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+enum SettingLabel {
+    Vsync,
+    ControlMode,
+}
+
+#[derive(Component, Clone, Copy)]
+enum MenuButton {
+    Start,
+    HighScores,
+    Settings,
+    Quit,
+    ToggleVsync,
+    ToggleControlMode,
+    Controls,
+    Rebind(Action),
+    ResetBindings,
+    Back,
+    Resume,
+    MainMenu,
+    Restart,
+}
+
+/// Main menu, settings, controls, high score, pause and game over screens.
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::MainMenu), spawn_main_menu_screen)
+            .add_systems(OnExit(AppState::MainMenu), despawn_screen::<MainMenuScreen>)
+            .add_systems(OnEnter(AppState::Settings), spawn_settings_screen)
+            .add_systems(OnExit(AppState::Settings), despawn_screen::<SettingsScreen>)
+            .add_systems(OnEnter(AppState::Controls), spawn_controls_screen)
+            .add_systems(
+                OnExit(AppState::Controls),
+                (despawn_screen::<ControlsScreen>, discard_rebinding),
+            )
+            .add_systems(OnEnter(AppState::HighScores), spawn_high_score_screen)
+            .add_systems(
+                OnExit(AppState::HighScores),
+                despawn_screen::<HighScoreScreen>,
+            )
+            .add_systems(OnEnter(AppState::Paused), (spawn_pause_screen, pause_time))
+            .add_systems(
+                OnExit(AppState::Paused),
+                (despawn_screen::<PauseScreen>, resume_time),
+            )
+            .add_systems(OnEnter(AppState::GameOver), spawn_game_over_screen)
+            .add_systems(
+                OnExit(AppState::GameOver),
+                (despawn_screen::<GameOverScreen>, discard_name_entry),
+            )
+            .add_systems(
+                Update,
+                (
+                    button_colors,
+                    menu_button_action,
+                    // Before the buttons so the click that starts rebinding
+                    // is not bound right away.
+                    capture_binding
+                        .run_if(resource_exists::<Rebinding>())
+                        .before(menu_button_action),
+                    update_binding_labels.run_if(in_state(AppState::Controls)),
+                    toggle_pause
+                        .run_if(in_state(AppState::InGame).or_else(in_state(AppState::Paused))),
+                    // Before the name entry so the Enter that saves the name
+                    // does not restart the game as well.
+                    restart_game
+                        .run_if(in_state(AppState::GameOver))
+                        .run_if(not(resource_exists::<NameEntry>()))
+                        .before(name_entry_input),
+                    name_entry_input,
+                ),
+            );
+    }
+}
+
+fn menu_root() -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(20.0),
+            ..default()
+        },
+        background_color: Color::rgba(0.0, 0.0, 0.0, 0.6).into(),
+        ..default()
+    }
+}
+
+fn menu_text(text: impl Into<String>, font_size: f32) -> TextBundle {
+    TextBundle::from_section(
+        text,
+        TextStyle {
+            font_size,
+            color: Color::WHITE,
+            ..default()
+        },
+    )
+}
+
+fn menu_button() -> ButtonBundle {
+    ButtonBundle {
+        style: Style {
+            width: Val::Px(200.0),
+            height: Val::Px(50.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        background_color: BUTTON_NORMAL_COLOR.into(),
+        ..default()
+    }
+}
+
+fn spawn_menu_button(parent: &mut ChildBuilder, label: &str, action: MenuButton) {
+    parent
+        .spawn(menu_button())
+        .insert(action)
+        .with_children(|parent| {
+            parent.spawn(menu_text(label, 28.0));
+        });
+}
+
+fn vsync_label(present_mode: PresentMode) -> String {
+    match present_mode {
+        PresentMode::AutoNoVsync | PresentMode::Immediate | PresentMode::Mailbox => {
+            "VSync: Off".to_string()
+        }
+        _ => "VSync: On".to_string(),
+    }
+}
+
+fn control_mode_label(control_mode: ControlMode) -> String {
+    match control_mode {
+        ControlMode::PointAndClick => "Controls: Point & Click".to_string(),
+        ControlMode::Classic => "Controls: Classic".to_string(),
+    }
+}
+
+fn binding_label(
+    action: Action,
+    bindings: &InputBindings,
+    rebinding: Option<&Rebinding>,
+) -> String {
+    match rebinding {
+        Some(rebinding) if rebinding.action == action => {
+            format!("{}: press a key or button, Escape cancels", action.name())
+        }
+        _ => format!("{}: {}", action.name(), bindings.label(action)),
+    }
+}
+
+fn save_bindings(bindings: &InputBindings) {
+    let path = bindings_path();
+    match bindings.save(&path) {
+        Ok(()) => info!("Saved bindings to {}", path.display()),
+        Err(err) => warn!("Could not save bindings to {}: {}", path.display(), err),
+    }
+}
+
+fn spawn_setting_button(
+    parent: &mut ChildBuilder,
+    label: String,
+    action: MenuButton,
+    setting: SettingLabel,
+) {
+    parent
+        .spawn(menu_button())
+        .insert(action)
+        .with_children(|parent| {
+            parent.spawn(menu_text(label, 28.0)).insert(setting);
+        });
+}
+
+fn spawn_main_menu_screen(mut commands: Commands) {
+    commands
+        .spawn(menu_root())
+        .insert(MainMenuScreen)
+        .with_children(|parent| {
+            parent.spawn(menu_text("Asteroids", 64.0));
+
+            spawn_menu_button(parent, "Start", MenuButton::Start);
+            spawn_menu_button(parent, "High Scores", MenuButton::HighScores);
+            spawn_menu_button(parent, "Settings", MenuButton::Settings);
+            spawn_menu_button(parent, "Quit", MenuButton::Quit);
+        });
+}
+
+fn spawn_settings_screen(
+    mut commands: Commands,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    control_mode: Res<ControlMode>,
+) {
+    let present_mode = window_query.single().present_mode;
+
+    commands
+        .spawn(menu_root())
+        .insert(SettingsScreen)
+        .with_children(|parent| {
+            parent.spawn(menu_text("Settings", 64.0));
+
+            spawn_setting_button(
+                parent,
+                vsync_label(present_mode),
+                MenuButton::ToggleVsync,
+                SettingLabel::Vsync,
+            );
+            spawn_setting_button(
+                parent,
+                control_mode_label(*control_mode),
+                MenuButton::ToggleControlMode,
+                SettingLabel::ControlMode,
+            );
+            spawn_menu_button(parent, "Key Bindings", MenuButton::Controls);
+
+            spawn_menu_button(parent, "Back", MenuButton::Back);
+        });
+}
+
+fn spawn_controls_screen(mut commands: Commands, bindings: Res<InputBindings>) {
+    commands
+        .spawn(menu_root())
+        .insert(ControlsScreen)
+        .with_children(|parent| {
+            parent.spawn(menu_text("Key Bindings", 64.0));
+            parent.spawn(menu_text(
+                "Click an action, then press the new key or button",
+                20.0,
+            ));
+
+            // Packed closer than the other buttons so all the actions fit.
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(6.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for action in Action::ALL {
+                        parent
+                            .spawn(ButtonBundle {
+                                style: Style {
+                                    width: Val::Px(560.0),
+                                    height: Val::Px(32.0),
+                                    align_items: AlignItems::Center,
+                                    justify_content: JustifyContent::Center,
+                                    ..default()
+                                },
+                                background_color: BUTTON_NORMAL_COLOR.into(),
+                                ..default()
+                            })
+                            .insert(MenuButton::Rebind(action))
+                            .with_children(|parent| {
+                                parent
+                                    .spawn(menu_text(binding_label(action, &bindings, None), 20.0))
+                                    .insert(BindingLabel(action));
+                            });
+                    }
+                });
+
+            spawn_menu_button(parent, "Defaults", MenuButton::ResetBindings);
+            spawn_menu_button(parent, "Back", MenuButton::Settings);
+        });
+}
+
+fn spawn_high_score_screen(mut commands: Commands, high_scores: Res<HighScores>) {
+    commands
+        .spawn(menu_root())
+        .insert(HighScoreScreen)
+        .with_children(|parent| {
+            parent.spawn(menu_text("High Scores", 64.0));
+
+            if high_scores.entries.is_empty() {
+                parent.spawn(menu_text("No high scores yet", 24.0));
+            }
+
+            for (rank, entry) in high_scores.entries.iter().enumerate() {
+                parent.spawn(menu_text(
+                    format!(
+                        "{}. {} - {} (Wave {})",
+                        rank + 1,
+                        entry.name,
+                        entry.score,
+                        entry.wave
+                    ),
+                    24.0,
+                ));
+            }
+
+            spawn_menu_button(parent, "Back", MenuButton::Back);
+        });
+}
+
+fn spawn_pause_screen(mut commands: Commands) {
+    commands
+        .spawn(menu_root())
+        .insert(PauseScreen)
+        .with_children(|parent| {
+            parent.spawn(menu_text("Paused", 64.0));
+
+            spawn_menu_button(parent, "Resume", MenuButton::Resume);
+            spawn_menu_button(parent, "Main Menu", MenuButton::MainMenu);
+        });
+}
+
+fn spawn_game_over_screen(
+    mut commands: Commands,
+    score: Res<Score>,
+    run_timer: Res<RunTimer>,
+    wave: Res<Wave>,
+    death: Res<Death>,
+    rng: Res<GameRng>,
+    high_scores: Res<HighScores>,
+    playback: Option<Res<ReplayPlayback>>,
+) {
+    let new_high_score = playback.is_none() && high_scores.qualifies(score.value);
+    if new_high_score {
+        commands.init_resource::<NameEntry>();
+    }
+
+    commands
+        .spawn(menu_root())
+        .insert(GameOverScreen)
+        .with_children(|parent| {
+            parent.spawn(menu_text("Game Over", 64.0));
+            if let Some(cause) = death.cause {
+                parent.spawn(menu_text(format!("Destroyed by a {}", cause), 24.0));
+            }
+            parent.spawn(menu_text(format!("Score: {}", score.value), 32.0));
+            parent.spawn(menu_text(
+                format!("Time: {}", format_run_time(&run_timer.stopwatch)),
+                32.0,
+            ));
+            parent.spawn(menu_text(format!("Wave: {}", wave.number), 32.0));
+            parent.spawn(menu_text(format!("Seed: {}", rng.seed), 20.0));
+
+            if new_high_score {
+                parent.spawn(menu_text("New high score! Type your name:", 24.0));
+                parent.spawn(menu_text("_", 32.0)).insert(NameEntryText);
+                parent.spawn(menu_text("Press Enter to save", 20.0));
+            }
+
+            spawn_menu_button(parent, "Restart", MenuButton::Restart);
+            spawn_menu_button(parent, "Main Menu", MenuButton::MainMenu);
+        });
+}
+
+fn button_colors(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, mut background_color) in button_query.iter_mut() {
+        *background_color = match interaction {
+            Interaction::Pressed => BUTTON_PRESSED_COLOR,
+            Interaction::Hovered => BUTTON_HOVERED_COLOR,
+            Interaction::None => BUTTON_NORMAL_COLOR,
+        }
+        .into();
+    }
+}
+
+fn menu_button_action(
+    button_query: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    mut label_query: Query<(&mut Text, &SettingLabel)>,
+    mut control_mode: ResMut<ControlMode>,
+    mut bindings: ResMut<InputBindings>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut exit: EventWriter<AppExit>,
+    mut commands: Commands,
+) {
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match button {
+            MenuButton::Start | MenuButton::Resume | MenuButton::Restart => {
+                next_state.set(AppState::InGame)
+            }
+            MenuButton::HighScores => next_state.set(AppState::HighScores),
+            MenuButton::Settings => next_state.set(AppState::Settings),
+            MenuButton::Back | MenuButton::MainMenu => next_state.set(AppState::MainMenu),
+            MenuButton::Quit => exit.send(AppExit),
+            MenuButton::ToggleVsync => {
+                let mut window = window_query.single_mut();
+                window.present_mode = match window.present_mode {
+                    PresentMode::AutoNoVsync => PresentMode::AutoVsync,
+                    _ => PresentMode::AutoNoVsync,
+                };
+
+                for (mut text, setting) in label_query.iter_mut() {
+                    if *setting == SettingLabel::Vsync {
+                        text.sections[0].value = vsync_label(window.present_mode);
+                    }
+                }
+            }
+            MenuButton::Controls => next_state.set(AppState::Controls),
+            // Aiming and steering follow a stick, there is nothing to press
+            // so the button switches the stick.
+            MenuButton::Rebind(action) if action.is_analog() => {
+                let right_stick = Binding::Stick(GamepadStick::Right);
+                let stick = if bindings.get(*action).contains(&right_stick) {
+                    Binding::Stick(GamepadStick::Left)
+                } else {
+                    right_stick
+                };
+                bindings.rebind(*action, stick);
+                save_bindings(&bindings);
+            }
+            MenuButton::Rebind(action) => commands.insert_resource(Rebinding { action: *action }),
+            MenuButton::ResetBindings => {
+                *bindings = InputBindings::default();
+                save_bindings(&bindings);
+            }
+            MenuButton::ToggleControlMode => {
+                *control_mode = match *control_mode {
+                    ControlMode::PointAndClick => ControlMode::Classic,
+                    ControlMode::Classic => ControlMode::PointAndClick,
+                };
+
+                for (mut text, setting) in label_query.iter_mut() {
+                    if *setting == SettingLabel::ControlMode {
+                        text.sections[0].value = control_mode_label(*control_mode);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Binds the next key or button pressed to the pending action, Escape
+/// cancels and clicks on the menu buttons are left to the buttons.
+fn capture_binding(
+    mut commands: Commands,
+    rebinding: Res<Rebinding>,
+    keys: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    interaction_query: Query<&Interaction>,
+    mut bindings: ResMut<InputBindings>,
+) {
+    if keys.just_pressed(KeyCode::Escape) {
+        commands.remove_resource::<Rebinding>();
+        return;
+    }
+
+    let over_button = interaction_query
+        .iter()
+        .any(|interaction| *interaction != Interaction::None);
+
+    let binding = keys
+        .get_just_pressed()
+        .next()
+        .map(|key| Binding::Key(*key))
+        .or_else(|| {
+            mouse_buttons
+                .get_just_pressed()
+                .next()
+                .filter(|_| !over_button)
+                .map(|button| Binding::Mouse(*button))
+        })
+        .or_else(|| {
+            gamepad_buttons
+                .get_just_pressed()
+                .next()
+                .map(|button| Binding::Gamepad(button.button_type))
+        });
+
+    let Some(binding) = binding else {
+        return;
+    };
+
+    bindings.rebind(rebinding.action, binding);
+    save_bindings(&bindings);
+    commands.remove_resource::<Rebinding>();
+}
+
+fn update_binding_labels(
+    bindings: Res<InputBindings>,
+    rebinding: Option<Res<Rebinding>>,
+    mut label_query: Query<(&mut Text, &BindingLabel)>,
+) {
+    for (mut text, label) in label_query.iter_mut() {
+        let value = binding_label(label.0, &bindings, rebinding.as_deref());
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+}
+
+fn discard_rebinding(mut commands: Commands) {
+    commands.remove_resource::<Rebinding>();
+}
+
+fn toggle_pause(
+    actions: ActionInput,
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if !actions.just_pressed(Action::Pause) {
+        return;
+    }
+
+    match state.get() {
+        AppState::InGame => next_state.set(AppState::Paused),
+        AppState::Paused => next_state.set(AppState::InGame),
+        _ => {}
+    }
+}
+
+fn pause_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+fn resume_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+fn restart_game(keys: Res<Input<KeyCode>>, mut next_state: ResMut<NextState<AppState>>) {
+    if keys.just_pressed(KeyCode::Return) {
+        next_state.set(AppState::InGame);
+    }
+}
+
+fn name_entry_input(
+    mut commands: Commands,
+    mut characters: EventReader<ReceivedCharacter>,
+    keys: Res<Input<KeyCode>>,
+    entry: Option<ResMut<NameEntry>>,
+    mut text_query: Query<&mut Text, With<NameEntryText>>,
+    mut high_scores: ResMut<HighScores>,
+    score: Res<Score>,
+    wave: Res<Wave>,
+) {
+    // Drop what was typed meanwhile, keys held while playing would end up in
+    // the name otherwise.
+    let Some(mut entry) = entry else {
+        characters.clear();
+        return;
+    };
+
+    for event in characters.read() {
+        if (event.char.is_alphanumeric() || event.char == ' ')
+            && entry.name.chars().count() < HIGH_SCORE_NAME_LENGTH
+        {
+            entry.name.push(event.char);
+        }
+    }
+
+    if keys.just_pressed(KeyCode::Back) {
+        entry.name.pop();
+    }
+
+    if keys.just_pressed(KeyCode::Return) {
+        let name = match entry.name.trim() {
+            "" => "Anonymous".to_string(),
+            name => name.to_string(),
+        };
+
+        high_scores.insert(HighScoreEntry {
+            name: name.clone(),
+            score: score.value,
+            wave: wave.number,
+        });
+
+        let path = high_score_path();
+        match high_scores.save(&path) {
+            Ok(()) => info!("Saved high scores to {}", path.display()),
+            Err(err) => warn!("Could not save high scores to {}: {}", path.display(), err),
+        }
+
+        for mut text in text_query.iter_mut() {
+            text.sections[0].value = format!("Saved as {}", name);
+        }
+        commands.remove_resource::<NameEntry>();
+        return;
+    }
+
+    if entry.is_changed() {
+        for mut text in text_query.iter_mut() {
+            text.sections[0].value = format!("{}_", entry.name);
+        }
+    }
+}
+
+fn discard_name_entry(mut commands: Commands) {
+    commands.remove_resource::<NameEntry>();
+}
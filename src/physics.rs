@@ -0,0 +1,55 @@
+use bevy::{prelude::*, transform::TransformSystem};
+
+use crate::{SimulationSet, WINDOW_HEIGHT, WINDOW_WIDTH};
+
+/// Position of an entity in the fixed timestep simulation.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Position(pub Vec2);
+
+/// `Position` at the start of the current tick, used to interpolate the
+/// rendered `Transform` between ticks.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct PreviousPosition(pub Vec2);
+
+/// Interpolates the `Transform` of simulated entities between fixed ticks.
+pub struct PhysicsPlugin;
+
+impl Plugin for PhysicsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            store_previous_positions.in_set(SimulationSet::Prepare),
+        )
+        .add_systems(
+            PostUpdate,
+            interpolate_transforms.before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// Wraps `position` around the edges of the play field.
+pub fn wrap_position(position: Vec2) -> Vec2 {
+    let half_size = Vec2::new(WINDOW_WIDTH, WINDOW_HEIGHT) / 2.0;
+    let size = half_size * 2.0;
+
+    (position + half_size).rem_euclid(size) - half_size
+}
+
+fn store_previous_positions(mut query: Query<(&Position, &mut PreviousPosition)>) {
+    for (position, mut previous) in query.iter_mut() {
+        previous.0 = position.0;
+    }
+}
+
+fn interpolate_transforms(
+    time: Res<Time<Fixed>>,
+    mut query: Query<(&mut Transform, &Position, &PreviousPosition)>,
+) {
+    let alpha = time.overstep_percentage();
+
+    for (mut transform, position, previous) in query.iter_mut() {
+        let translation = previous.0.lerp(position.0, alpha);
+        transform.translation.x = translation.x;
+        transform.translation.y = translation.y;
+    }
+}
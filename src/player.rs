@@ -0,0 +1,406 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::{
+    asteroid::{Asteroid, AsteroidSize, MovementPattern},
+    bullet::{spawn_bullet, FireCooldown},
+    config::GameConfig,
+    events::{AsteroidDestroyed, DeathCause, DestroyCause, PlayerDied, PlayerHit, ShotFired},
+    input::PlayerInput,
+    physics::{wrap_position, Position, PreviousPosition},
+    powerup::ActivePowerUps,
+    ResetGame, SimulationSet,
+};
+
+pub const PLAYER_WIDTH: f32 = 25.0;
+pub const PLAYER_HEIGHT: f32 = 50.0;
+
+pub const PLAYER_LIVES: u32 = 3;
+pub const PLAYER_INVULNERABILITY: f32 = 2.0;
+pub const PLAYER_RESPAWN_CLEAR_RADIUS: f32 = 150.0;
+const PLAYER_BLINK_INTERVAL: f32 = 0.1;
+
+pub const SHIP_THRUST: f32 = 300.0;
+pub const SHIP_TURN_SPEED: f32 = 4.0;
+pub const SHIP_DRAG: f32 = 0.5;
+pub const SHIP_MAX_SPEED: f32 = 300.0;
+
+#[derive(Component)]
+pub struct Player;
+
+#[derive(Component, Default)]
+pub struct ShipVelocity(pub Vec2);
+
+/// Ship that cannot be hit, blinking until `timer` finishes.
+#[derive(Component)]
+pub struct Invulnerable {
+    pub timer: Timer,
+}
+
+impl Invulnerable {
+    pub fn new(duration: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+        }
+    }
+}
+
+/// Ships left in the current run, the run ends when the last one is hit.
+#[derive(Resource)]
+pub struct Lives {
+    pub value: u32,
+}
+
+impl Lives {
+    pub fn new(lives: u32) -> Self {
+        Self { value: lives }
+    }
+}
+
+impl FromWorld for Lives {
+    fn from_world(world: &mut World) -> Self {
+        Self::new(world.resource::<GameConfig>().player_lives)
+    }
+}
+
+/// What ended the current run, set once when the last ship is hit.
+#[derive(Default, Resource)]
+pub struct Death {
+    pub cause: Option<DeathCause>,
+}
+
+/// How the ship is controlled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource, Serialize, Deserialize)]
+pub enum ControlMode {
+    /// The ship stays in the center, turns towards the mouse and shoots on
+    /// click.
+    #[default]
+    PointAndClick,
+    /// The ship turns, thrusts and drifts with inertia, wrapping around the
+    /// screen edges, and shoots straight ahead.
+    Classic,
+}
+
+/// Spawns the ship and handles aiming, flying, shooting and dying.
+pub struct PlayerPlugin;
+
+impl Plugin for PlayerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ControlMode>()
+            .init_resource::<Lives>()
+            .init_resource::<Death>()
+            .add_systems(Startup, spawn_player)
+            .add_systems(
+                FixedUpdate,
+                (
+                    (
+                        player_rotation.run_if(resource_equals(ControlMode::PointAndClick)),
+                        ship_movement.run_if(resource_equals(ControlMode::Classic)),
+                        player_shooting,
+                        tick_invulnerability,
+                    )
+                        .chain()
+                        .in_set(SimulationSet::Player),
+                    player_collision.in_set(SimulationSet::ShipCollision),
+                ),
+            )
+            .add_systems(Update, blink_invulnerable)
+            .add_systems(ResetGame, reset_player);
+    }
+}
+
+/// Direction the nose of the ship points at.
+pub fn ship_forward(transform: &Transform) -> Vec2 {
+    (transform.rotation * Vec3::NEG_Y).truncate()
+}
+
+fn spawn_player(mut commands: Commands, config: Res<GameConfig>) {
+    commands
+        .spawn(SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgb(0.0, 0.0, 1.0),
+                custom_size: Some(Vec2::new(config.player_width, config.player_height)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, 0.0),
+            ..default()
+        })
+        .insert(Position::default())
+        .insert(PreviousPosition::default())
+        .insert(ShipVelocity::default())
+        .insert(Player);
+}
+
+fn player_rotation(
+    input: Res<PlayerInput>,
+    mut transform_query: Query<&mut Transform, With<Player>>,
+) {
+    if let Some(aim) = input.aim {
+        for mut transform in transform_query.iter_mut() {
+            transform.rotation = Quat::from_rotation_z(aim.x.atan2(-aim.y));
+        }
+    }
+}
+
+fn ship_movement(
+    time: Res<Time>,
+    input: Res<PlayerInput>,
+    config: Res<GameConfig>,
+    mut ship_query: Query<
+        (
+            &mut Transform,
+            &mut Position,
+            &mut PreviousPosition,
+            &mut ShipVelocity,
+        ),
+        With<Player>,
+    >,
+) {
+    let dt = time.delta_seconds();
+
+    for (mut transform, mut position, mut previous, mut velocity) in ship_query.iter_mut() {
+        transform.rotate_z(input.turn.clamp(-1.0, 1.0) * config.ship_turn_speed * dt);
+
+        if input.thrust {
+            velocity.0 += ship_forward(&transform) * config.ship_thrust * dt;
+        }
+
+        velocity.0 *= (-config.ship_drag * dt).exp();
+        velocity.0 = velocity.0.clamp_length_max(config.ship_max_speed);
+
+        let moved = position.0 + velocity.0 * dt;
+        let wrapped = wrap_position(moved);
+
+        // Shift the interpolation start along with the wrap so the ship
+        // does not slide across the whole screen.
+        previous.0 += wrapped - moved;
+        position.0 = wrapped;
+    }
+}
+
+fn player_shooting(
+    time: Res<Time>,
+    mut commands: Commands,
+    input: Res<PlayerInput>,
+    mut cooldown: ResMut<FireCooldown>,
+    ship_query: Query<(&Transform, &Position, &ShipVelocity), With<Player>>,
+    power_ups: Res<ActivePowerUps>,
+    config: Res<GameConfig>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut fired: EventWriter<ShotFired>,
+    mut shots: Local<u32>,
+) {
+    cooldown
+        .timer
+        .tick(Duration::from_secs_f32(time.delta_seconds()));
+
+    if !input.fire || !cooldown.timer.finished() {
+        return;
+    }
+
+    let Ok((transform, position, velocity)) = ship_query.get_single() else {
+        return;
+    };
+    let forward = ship_forward(transform);
+    let nose = position.0 + forward * config.player_height / 2.0;
+    *shots += 1;
+
+    let angles: &[f32] = if power_ups.has_multishot() {
+        &[-1.0, 0.0, 1.0]
+    } else {
+        &[0.0]
+    };
+
+    for angle in angles {
+        let direction = Vec2::from_angle(angle * config.power_up_multishot_spread).rotate(forward);
+
+        spawn_bullet(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            &config,
+            nose,
+            velocity.0 + direction * config.bullet_speed,
+            *shots,
+        );
+    }
+
+    fired.send(ShotFired {
+        shot: *shots,
+        position: nose,
+        direction: forward,
+    });
+    cooldown.timer.reset();
+}
+
+fn tick_invulnerability(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut ship_query: Query<(Entity, &mut Invulnerable, &mut Visibility), With<Player>>,
+) {
+    for (entity, mut invulnerable, mut visibility) in ship_query.iter_mut() {
+        invulnerable
+            .timer
+            .tick(Duration::from_secs_f32(time.delta_seconds()));
+
+        if invulnerable.timer.finished() {
+            commands.entity(entity).remove::<Invulnerable>();
+            *visibility = Visibility::Inherited;
+        }
+    }
+}
+
+fn blink_invulnerable(mut ship_query: Query<(&Invulnerable, &mut Visibility), With<Player>>) {
+    for (invulnerable, mut visibility) in ship_query.iter_mut() {
+        let blink = (invulnerable.timer.elapsed_secs() / PLAYER_BLINK_INTERVAL) as u32;
+
+        *visibility = if blink.is_multiple_of(2) {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+    }
+}
+
+fn player_collision(
+    mut commands: Commands,
+    mut ship_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Position,
+            &mut PreviousPosition,
+            &mut ShipVelocity,
+        ),
+        (With<Player>, Without<Invulnerable>),
+    >,
+    asteroid_query: Query<
+        (Entity, &Position, &AsteroidSize, &MovementPattern),
+        (With<Asteroid>, Without<Player>),
+    >,
+    mut lives: ResMut<Lives>,
+    mut death: ResMut<Death>,
+    mut power_ups: ResMut<ActivePowerUps>,
+    mut hits: EventWriter<PlayerHit>,
+    mut deaths: EventWriter<PlayerDied>,
+    mut destroyed: EventWriter<AsteroidDestroyed>,
+    config: Res<GameConfig>,
+) {
+    // The state only changes after the tick, more ticks can run until then.
+    if death.cause.is_some() {
+        return;
+    }
+
+    let Ok((ship, mut transform, mut position, mut previous, mut velocity)) =
+        ship_query.get_single_mut()
+    else {
+        return;
+    };
+
+    let hit = asteroid_query
+        .iter()
+        .find(|(_, asteroid, size, _)| asteroid.0.distance(position.0) < size.radius(&config));
+
+    let Some((asteroid, asteroid_position, size, pattern)) = hit else {
+        return;
+    };
+
+    if power_ups.has_shield() {
+        info!("Shield absorbed a hit");
+        power_ups.shield = None;
+        commands.entity(asteroid).despawn();
+        destroyed.send(AsteroidDestroyed {
+            entity: asteroid,
+            position: asteroid_position.0,
+            size: *size,
+            cause: DestroyCause::Shield,
+        });
+        hits.send(PlayerHit {
+            asteroid,
+            position: position.0,
+            shielded: true,
+            lives_left: lives.value,
+        });
+        return;
+    }
+
+    lives.value = lives.value.saturating_sub(1);
+    hits.send(PlayerHit {
+        asteroid,
+        position: position.0,
+        shielded: false,
+        lives_left: lives.value,
+    });
+
+    if lives.value == 0 {
+        let cause = DeathCause {
+            size: *size,
+            pattern: *pattern,
+        };
+        death.cause = Some(cause);
+        deaths.send(PlayerDied {
+            asteroid,
+            position: position.0,
+            cause,
+        });
+        return;
+    }
+
+    info!("Ship destroyed, {} lives left", lives.value);
+
+    // Respawn in the center with some room to get going again.
+    *transform = Transform::default();
+    position.0 = Vec2::ZERO;
+    previous.0 = Vec2::ZERO;
+    velocity.0 = Vec2::ZERO;
+
+    for (asteroid, asteroid_position, size, _) in asteroid_query.iter() {
+        if asteroid_position.0.length() < config.player_respawn_clear_radius {
+            commands.entity(asteroid).despawn();
+            destroyed.send(AsteroidDestroyed {
+                entity: asteroid,
+                position: asteroid_position.0,
+                size: *size,
+                cause: DestroyCause::Respawn,
+            });
+        }
+    }
+
+    commands
+        .entity(ship)
+        .insert(Invulnerable::new(config.player_invulnerability));
+}
+
+fn reset_player(
+    mut commands: Commands,
+    mut ship_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Position,
+            &mut PreviousPosition,
+            &mut ShipVelocity,
+            &mut Visibility,
+        ),
+        With<Player>,
+    >,
+    mut lives: ResMut<Lives>,
+    mut death: ResMut<Death>,
+    config: Res<GameConfig>,
+) {
+    for (entity, mut transform, mut position, mut previous, mut velocity, mut visibility) in
+        ship_query.iter_mut()
+    {
+        *transform = Transform::default();
+        position.0 = Vec2::ZERO;
+        previous.0 = Vec2::ZERO;
+        velocity.0 = Vec2::ZERO;
+        *visibility = Visibility::Inherited;
+        commands.entity(entity).remove::<Invulnerable>();
+    }
+
+    *lives = Lives::new(config.player_lives);
+    *death = Death::default();
+}
@@ -0,0 +1,254 @@
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use rand::prelude::*;
+use std::time::Duration;
+
+use crate::{
+    asteroid::{Asteroid, AsteroidSize},
+    bullet::Bullet,
+    config::GameConfig,
+    events::{AsteroidDestroyed, DestroyCause},
+    input::PlayerInput,
+    physics::{Position, PreviousPosition},
+    player::Player,
+    ResetGame, SimulationSet,
+};
+
+pub const POWER_UP_DROP_CHANCE: f64 = 0.1;
+pub const POWER_UP_RADIUS: f32 = 12.0;
+pub const POWER_UP_LIFETIME: f32 = 8.0;
+pub const POWER_UP_DURATION: f32 = 10.0;
+pub const POWER_UP_SLOW_TIME_FACTOR: f32 = 0.5;
+pub const POWER_UP_MULTISHOT_SPREAD: f32 = 0.25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerUpKind {
+    /// Absorbs the next asteroid that hits the ship.
+    Shield,
+    /// Fires three bullets in a spread.
+    Multishot,
+    /// Slows down the asteroids.
+    SlowTime,
+    /// Adds a bomb, used with the bomb action to destroy every asteroid at
+    /// once.
+    Bomb,
+}
+
+impl PowerUpKind {
+    const ALL: [PowerUpKind; 4] = [
+        PowerUpKind::Shield,
+        PowerUpKind::Multishot,
+        PowerUpKind::SlowTime,
+        PowerUpKind::Bomb,
+    ];
+
+    fn color(&self) -> Color {
+        match self {
+            PowerUpKind::Shield => Color::CYAN,
+            PowerUpKind::Multishot => Color::ORANGE,
+            PowerUpKind::SlowTime => Color::LIME_GREEN,
+            PowerUpKind::Bomb => Color::RED,
+        }
+    }
+}
+
+/// Pickup waiting for the ship to fly into it or shoot it, it disappears when
+/// `lifetime` finishes.
+#[derive(Component)]
+pub struct PowerUp {
+    pub kind: PowerUpKind,
+    pub lifetime: Timer,
+}
+
+/// Timed effects of the collected power-ups, an effect is active while its
+/// timer is set, and the bombs held.
+#[derive(Default, Resource)]
+pub struct ActivePowerUps {
+    pub shield: Option<Timer>,
+    pub multishot: Option<Timer>,
+    pub slow_time: Option<Timer>,
+    pub bombs: u32,
+}
+
+impl ActivePowerUps {
+    pub fn has_shield(&self) -> bool {
+        self.shield.is_some()
+    }
+
+    pub fn has_multishot(&self) -> bool {
+        self.multishot.is_some()
+    }
+
+    /// Factor of the asteroid speed.
+    pub fn time_scale(&self, config: &GameConfig) -> f32 {
+        if self.slow_time.is_some() {
+            config.power_up_slow_time_factor
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Drops power-ups from destroyed asteroids, collects them, runs out their
+/// effects and sets off bombs.
+pub struct PowerUpPlugin;
+
+impl Plugin for PowerUpPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActivePowerUps>()
+            .add_systems(
+                FixedUpdate,
+                (detonate_bomb, power_up_pickup, tick_power_ups)
+                    .chain()
+                    .in_set(SimulationSet::Progression),
+            )
+            .add_systems(ResetGame, reset_power_ups);
+    }
+}
+
+/// Spawns a random power-up at `position` with a `power_up_drop_chance`.
+pub fn maybe_drop_power_up(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    config: &GameConfig,
+    rng: &mut impl Rng,
+    position: Vec2,
+) {
+    if !rng.gen_bool(config.power_up_drop_chance.clamp(0.0, 1.0)) {
+        return;
+    }
+
+    let Some(&kind) = PowerUpKind::ALL.choose(rng) else {
+        return;
+    };
+
+    commands
+        .spawn(MaterialMesh2dBundle {
+            mesh: meshes
+                .add(shape::Circle::new(config.power_up_radius).into())
+                .into(),
+            material: materials.add(ColorMaterial::from(kind.color())),
+            transform: Transform::from_translation(position.extend(0.5)),
+            ..default()
+        })
+        .insert(Position(position))
+        .insert(PreviousPosition(position))
+        .insert(PowerUp {
+            kind,
+            lifetime: Timer::from_seconds(config.power_up_lifetime, TimerMode::Once),
+        });
+}
+
+fn power_up_pickup(
+    mut commands: Commands,
+    ship_query: Query<&Position, With<Player>>,
+    bullet_query: Query<(Entity, &Position), With<Bullet>>,
+    power_up_query: Query<(Entity, &Position, &PowerUp)>,
+    mut active: ResMut<ActivePowerUps>,
+    config: Res<GameConfig>,
+) {
+    let ship_distance = config.power_up_radius + config.player_width / 2.0;
+    let bullet_distance = config.power_up_radius + config.bullet_radius;
+    let mut used_bullets = Vec::new();
+
+    for (entity, position, power_up) in power_up_query.iter() {
+        let touched = ship_query
+            .iter()
+            .any(|ship| ship.0.distance(position.0) < ship_distance);
+        let bullet = bullet_query.iter().find(|(bullet, bullet_position)| {
+            !used_bullets.contains(bullet)
+                && bullet_position.0.distance(position.0) < bullet_distance
+        });
+
+        if !touched && bullet.is_none() {
+            continue;
+        }
+
+        if let Some((bullet, _)) = bullet {
+            used_bullets.push(bullet);
+            commands.entity(bullet).despawn();
+        }
+
+        info!("Picked up {:?}", power_up.kind);
+        commands.entity(entity).despawn();
+
+        let effect = Some(Timer::from_seconds(
+            config.power_up_duration,
+            TimerMode::Once,
+        ));
+
+        match power_up.kind {
+            PowerUpKind::Shield => active.shield = effect,
+            PowerUpKind::Multishot => active.multishot = effect,
+            PowerUpKind::SlowTime => active.slow_time = effect,
+            PowerUpKind::Bomb => active.bombs += 1,
+        }
+    }
+}
+
+fn detonate_bomb(
+    mut commands: Commands,
+    input: Res<PlayerInput>,
+    asteroid_query: Query<(Entity, &Position, &AsteroidSize), With<Asteroid>>,
+    mut active: ResMut<ActivePowerUps>,
+    mut destroyed: EventWriter<AsteroidDestroyed>,
+) {
+    if !input.bomb || active.bombs == 0 {
+        return;
+    }
+
+    info!("Bomb detonated");
+    active.bombs -= 1;
+
+    for (asteroid, position, size) in asteroid_query.iter() {
+        commands.entity(asteroid).despawn();
+        destroyed.send(AsteroidDestroyed {
+            entity: asteroid,
+            position: position.0,
+            size: *size,
+            cause: DestroyCause::Bomb,
+        });
+    }
+}
+
+fn tick_power_ups(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut power_up_query: Query<(Entity, &mut PowerUp)>,
+    mut active: ResMut<ActivePowerUps>,
+) {
+    let delta = Duration::from_secs_f32(time.delta_seconds());
+
+    for (entity, mut power_up) in power_up_query.iter_mut() {
+        power_up.lifetime.tick(delta);
+
+        if power_up.lifetime.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    let active = &mut *active;
+    for effect in [
+        &mut active.shield,
+        &mut active.multishot,
+        &mut active.slow_time,
+    ] {
+        if let Some(timer) = effect {
+            if timer.tick(delta).finished() {
+                *effect = None;
+            }
+        }
+    }
+}
+
+fn reset_power_ups(
+    mut commands: Commands,
+    power_up_query: Query<Entity, With<PowerUp>>,
+    mut active: ResMut<ActivePowerUps>,
+) {
+    for entity in power_up_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    *active = ActivePowerUps::default();
+}
@@ -0,0 +1,252 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    config::{GameConfig, LiveConfig},
+    input::PlayerInput,
+    player::ControlMode,
+    rng::GameRng,
+    ron_file::{load_ron, save_ron_compact, RonFileError},
+    score::Score,
+    AppState, ResetGame, SimulationSet,
+};
+
+pub const REPLAY_DIRECTORY: &str = "replays";
+
+/// Everything needed to reproduce a run: the RNG seed, the tick rate, the
+/// control mode, the game config with its reloads and the input of every
+/// fixed tick, plus the final score to check the playback against.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ReplayFile {
+    pub seed: u64,
+    pub tick_rate: f64,
+    #[serde(default)]
+    pub control_mode: ControlMode,
+    /// Config the run was played with, older replays without one use the
+    /// current config.
+    #[serde(default)]
+    pub config: Option<GameConfig>,
+    #[serde(default)]
+    pub config_changes: Vec<ConfigChange>,
+    pub score: u32,
+    pub ticks: Vec<PlayerInput>,
+}
+
+/// Config reloaded in the middle of a run, used from the tick at index `tick`
+/// on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigChange {
+    pub tick: usize,
+    pub config: GameConfig,
+}
+
+impl ReplayFile {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RonFileError> {
+        load_ron(path)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), RonFileError> {
+        save_ron_compact(path, self)
+    }
+}
+
+/// Config and inputs of the current run, saved to `REPLAY_DIRECTORY` when it
+/// ends.
+#[derive(Default, Resource)]
+struct ReplayRecording {
+    config: Option<GameConfig>,
+    config_changes: Vec<ConfigChange>,
+    ticks: Vec<PlayerInput>,
+}
+
+/// Replay being played back instead of the live input.
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    pub replay: ReplayFile,
+    tick: usize,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: ReplayFile) -> Self {
+        Self { replay, tick: 0 }
+    }
+}
+
+/// Plays back a `ReplayPlayback` when one is inserted.
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                play_replay_input.in_set(SimulationSet::Input),
+                apply_replay_config_changes
+                    .after(SimulationSet::Events)
+                    .run_if(in_state(AppState::InGame)),
+            )
+                .run_if(resource_exists::<ReplayPlayback>()),
+        )
+        .add_systems(
+            Startup,
+            use_replay_settings.run_if(resource_exists::<ReplayPlayback>()),
+        )
+        .add_systems(
+            Update,
+            start_replay
+                .run_if(in_state(AppState::MainMenu))
+                .run_if(resource_exists::<ReplayPlayback>()),
+        )
+        .add_systems(
+            OnEnter(AppState::GameOver),
+            check_replay_score.run_if(resource_exists::<ReplayPlayback>()),
+        )
+        .add_systems(ResetGame, reset_playback);
+    }
+}
+
+/// Records the input of every run and saves it when the run ends.
+pub struct ReplayRecorderPlugin;
+
+impl Plugin for ReplayRecorderPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplayRecording>()
+            .add_systems(
+                FixedUpdate,
+                record_input
+                    .in_set(SimulationSet::Player)
+                    .run_if(not(resource_exists::<ReplayPlayback>())),
+            )
+            .add_systems(
+                OnEnter(AppState::GameOver),
+                save_recording.run_if(not(resource_exists::<ReplayPlayback>())),
+            )
+            .add_systems(ResetGame, reset_recording);
+    }
+}
+
+fn record_input(
+    input: Res<PlayerInput>,
+    config: Res<GameConfig>,
+    mut recording: ResMut<ReplayRecording>,
+) {
+    // A reload between two ticks takes effect from this one on.
+    if recording.config.is_none() {
+        recording.config = Some(config.clone());
+    } else if config.is_changed() {
+        let tick = recording.ticks.len();
+        recording.config_changes.push(ConfigChange {
+            tick,
+            config: config.clone(),
+        });
+    }
+
+    recording.ticks.push(*input);
+}
+
+fn play_replay_input(mut playback: ResMut<ReplayPlayback>, mut input: ResMut<PlayerInput>) {
+    *input = playback
+        .replay
+        .ticks
+        .get(playback.tick)
+        .copied()
+        .unwrap_or_default();
+    playback.tick += 1;
+}
+
+/// Switches to the reloaded configs at the end of the tick before the one
+/// they were recorded at, where the live game reloads them, so a new tick
+/// rate already holds for that tick.
+fn apply_replay_config_changes(playback: Res<ReplayPlayback>, mut live: LiveConfig) {
+    for change in &playback.replay.config_changes {
+        if change.tick != playback.tick {
+            continue;
+        }
+
+        match change.config.validate() {
+            Ok(()) => live.apply(&change.config),
+            Err(err) => warn!("Invalid config change in the replay, ignoring it: {}", err),
+        }
+    }
+}
+
+fn start_replay(mut next_state: ResMut<NextState<AppState>>) {
+    next_state.set(AppState::InGame);
+}
+
+/// Runs before the first `ResetGame`, which sets up the run from the config.
+fn use_replay_settings(
+    playback: Res<ReplayPlayback>,
+    mut time: ResMut<Time<Fixed>>,
+    mut control_mode: ResMut<ControlMode>,
+    mut config: ResMut<GameConfig>,
+) {
+    if let Some(replay_config) = &playback.replay.config {
+        match replay_config.validate() {
+            Ok(()) => *config = replay_config.clone(),
+            Err(err) => warn!(
+                "Invalid config in the replay, using the current one: {}",
+                err
+            ),
+        }
+    }
+
+    time.set_timestep_hz(playback.replay.tick_rate);
+    *control_mode = playback.replay.control_mode;
+}
+
+fn save_recording(
+    recording: Res<ReplayRecording>,
+    rng: Res<GameRng>,
+    score: Res<Score>,
+    config: Res<GameConfig>,
+    control_mode: Res<ControlMode>,
+) {
+    let config = recording.config.clone().unwrap_or_else(|| config.clone());
+
+    let replay = ReplayFile {
+        seed: rng.seed,
+        tick_rate: config.tick_rate,
+        control_mode: *control_mode,
+        config: Some(config),
+        config_changes: recording.config_changes.clone(),
+        score: score.value,
+        ticks: recording.ticks.clone(),
+    };
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let path = Path::new(REPLAY_DIRECTORY).join(format!("run-{}.ron", timestamp));
+
+    match replay.save(&path) {
+        Ok(()) => info!("Saved replay to {}", path.display()),
+        Err(err) => warn!("Could not save replay to {}: {}", path.display(), err),
+    }
+}
+
+fn check_replay_score(playback: Res<ReplayPlayback>, score: Res<Score>) {
+    if score.value == playback.replay.score {
+        info!("Replay finished with the recorded score {}", score.value);
+    } else {
+        warn!(
+            "Replay finished with score {} but {} was recorded",
+            score.value, playback.replay.score
+        );
+    }
+}
+
+fn reset_playback(playback: Option<ResMut<ReplayPlayback>>) {
+    if let Some(mut playback) = playback {
+        playback.tick = 0;
+    }
+}
+
+fn reset_recording(mut recording: ResMut<ReplayRecording>) {
+    *recording = ReplayRecording::default();
+}
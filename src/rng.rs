@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+use crate::ResetGame;
+
+/// Random number generator used by all gameplay code so a run can be replayed
+/// from its seed.
+#[derive(Resource)]
+pub struct GameRng {
+    pub seed: u64,
+    fixed: bool,
+    rng: ChaCha8Rng,
+}
+
+impl GameRng {
+    /// Every run uses the same `seed`.
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            fixed: true,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Every run picks a new random seed.
+    pub fn from_entropy() -> Self {
+        let seed = rand::thread_rng().gen();
+
+        Self {
+            seed,
+            fixed: false,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    fn reseed(&mut self) {
+        if !self.fixed {
+            self.seed = rand::thread_rng().gen();
+        }
+
+        self.rng = ChaCha8Rng::seed_from_u64(self.seed);
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::from_entropy()
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+/// Provides the `GameRng` resource, insert one before adding the plugin to
+/// choose the seed.
+pub struct RngPlugin;
+
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameRng>()
+            .add_systems(ResetGame, reset_rng);
+    }
+}
+
+fn reset_rng(mut rng: ResMut<GameRng>) {
+    rng.reseed();
+    info!("Starting a new run with seed {}", rng.seed);
+}
@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{fs, io, path::Path};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RonFileError {
+    #[error("could not access the file: {0}")]
+    Io(#[from] io::Error),
+    #[error("could not parse the file: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("could not serialize the contents: {0}")]
+    Serialize(#[from] ron::Error),
+}
+
+impl RonFileError {
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, RonFileError::Io(err) if err.kind() == io::ErrorKind::NotFound)
+    }
+}
+
+pub fn load_ron<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<T, RonFileError> {
+    let contents = fs::read_to_string(path)?;
+    Ok(ron::from_str(&contents)?)
+}
+
+/// Like `load_ron` but falls back to the default when the file is missing,
+/// or with a warning when it cannot be loaded.
+pub fn load_ron_or_default<T: DeserializeOwned + Default>(path: &Path, name: &str) -> T {
+    match load_ron(path) {
+        Ok(value) => value,
+        Err(err) if err.is_not_found() => T::default(),
+        Err(err) => {
+            warn!("Could not load {} from {}: {}", name, path.display(), err);
+            T::default()
+        }
+    }
+}
+
+/// Writes `value` in the readable format, creating the missing directories.
+pub fn save_ron<T: Serialize>(path: impl AsRef<Path>, value: &T) -> Result<(), RonFileError> {
+    write_ron(path.as_ref(), ron::ser::to_string_pretty(value, default())?)
+}
+
+/// Writes `value` on a single line, for the files too long to read anyway.
+pub fn save_ron_compact<T: Serialize>(
+    path: impl AsRef<Path>,
+    value: &T,
+) -> Result<(), RonFileError> {
+    write_ron(path.as_ref(), ron::to_string(value)?)
+}
+
+fn write_ron(path: &Path, contents: String) -> Result<(), RonFileError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, contents)?;
+    Ok(())
+}
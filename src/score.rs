@@ -0,0 +1,315 @@
+use bevy::{prelude::*, utils::HashMap};
+use std::time::Duration;
+
+use crate::{
+    bullet::Bullet,
+    config::GameConfig,
+    events::{AsteroidDestroyed, BulletMissed, DestroyCause, GameOverEvent, PlayerDied},
+    ResetGame, SimulationSet,
+};
+
+pub const COMBO_TIMEOUT: f32 = 2.0;
+pub const COMBO_HITS_PER_MULTIPLIER: u32 = 5;
+pub const COMBO_MAX_MULTIPLIER: u32 = 5;
+pub const COMBO_MULTI_KILL_BONUS: u32 = 5;
+
+const SCORE_POPUP_LIFETIME: f32 = 0.8;
+const SCORE_POPUP_RISE_SPEED: f32 = 40.0;
+const SCORE_POPUP_FONT_SIZE: f32 = 20.0;
+
+#[derive(Default, Resource)]
+pub struct Score {
+    pub value: u32,
+}
+
+/// Consecutive hits without a miss, raising the score multiplier. The combo
+/// breaks when a bullet misses or no hit lands for `combo_timeout` seconds.
+#[derive(Resource)]
+pub struct Combo {
+    pub hits: u32,
+    pub timer: Timer,
+    /// Asteroids destroyed by every shot that still has bullets in play, to
+    /// reward multi-kills and ignore the misses of shots that hit.
+    shot_kills: HashMap<u32, u32>,
+}
+
+impl Combo {
+    pub fn new(timeout: f32) -> Self {
+        Self {
+            hits: 0,
+            timer: Timer::from_seconds(timeout, TimerMode::Once),
+            shot_kills: HashMap::default(),
+        }
+    }
+
+    pub fn multiplier(&self, config: &GameConfig) -> u32 {
+        let steps = self.hits / config.combo_hits_per_multiplier.max(1);
+        (1 + steps).min(config.combo_max_multiplier.max(1))
+    }
+
+    /// Counts a hit by a bullet of `shot` and returns the points for
+    /// destroying an asteroid worth `points`.
+    pub fn hit(&mut self, shot: u32, points: u32, config: &GameConfig) -> u32 {
+        self.hits += 1;
+        self.timer.reset();
+
+        let kills = self.shot_kills.entry(shot).or_default();
+        *kills += 1;
+
+        let bonus = config.combo_multi_kill_bonus * (*kills - 1);
+        points * self.multiplier(config) + bonus
+    }
+
+    /// Breaks the combo for a bullet of `shot` that hit nothing, unless
+    /// another bullet of the same shot did.
+    pub fn miss(&mut self, shot: u32) {
+        if !self.shot_kills.contains_key(&shot) {
+            self.reset();
+        }
+    }
+
+    /// Forgets the shots `in_play` no longer holds, once all their bullets
+    /// are gone.
+    pub fn retain_shots(&mut self, in_play: impl Fn(u32) -> bool) {
+        self.shot_kills.retain(|shot, _| in_play(*shot));
+    }
+
+    pub fn reset(&mut self) {
+        self.hits = 0;
+    }
+}
+
+impl FromWorld for Combo {
+    fn from_world(world: &mut World) -> Self {
+        Self::new(world.resource::<GameConfig>().combo_timeout)
+    }
+}
+
+/// Points floating up from where they were scored.
+#[derive(Component)]
+pub struct ScorePopup {
+    pub lifetime: Timer,
+}
+
+/// Keeps track of the score and combo of the current run from the destroyed
+/// asteroids and missed bullets, and ends the run with the final score.
+pub struct ScorePlugin;
+
+impl Plugin for ScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Score>()
+            .init_resource::<Combo>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    combo_timeout.in_set(SimulationSet::Progression),
+                    (score_asteroids, end_run)
+                        .chain()
+                        .in_set(SimulationSet::Events),
+                ),
+            )
+            .add_systems(Update, animate_score_popups)
+            .add_systems(ResetGame, reset_score);
+    }
+}
+
+pub fn spawn_score_popup(commands: &mut Commands, position: Vec2, points: u32) {
+    commands
+        .spawn(Text2dBundle {
+            text: Text::from_section(
+                format!("+{}", points),
+                TextStyle {
+                    font_size: SCORE_POPUP_FONT_SIZE,
+                    color: Color::YELLOW,
+                    ..default()
+                },
+            ),
+            transform: Transform::from_translation(position.extend(10.0)),
+            ..default()
+        })
+        .insert(ScorePopup {
+            lifetime: Timer::from_seconds(SCORE_POPUP_LIFETIME, TimerMode::Once),
+        });
+}
+
+fn score_asteroids(
+    mut commands: Commands,
+    mut missed: EventReader<BulletMissed>,
+    mut destroyed: EventReader<AsteroidDestroyed>,
+    mut score: ResMut<Score>,
+    mut combo: ResMut<Combo>,
+    bullet_query: Query<&Bullet>,
+    config: Res<GameConfig>,
+) {
+    // Bullets expire while moving, before any hit of the tick is checked.
+    for event in missed.read() {
+        combo.miss(event.shot);
+    }
+
+    for event in destroyed.read() {
+        let points = match event.cause {
+            DestroyCause::Bullet { shot } => combo.hit(shot, event.size.score(), &config),
+            DestroyCause::Bomb => event.size.score(),
+            DestroyCause::Shield | DestroyCause::Respawn => continue,
+        };
+
+        score.value += points;
+        spawn_score_popup(&mut commands, event.position, points);
+    }
+
+    // The bullets that expired or hit this tick are despawned by now.
+    combo.retain_shots(|shot| bullet_query.iter().any(|bullet| bullet.shot == shot));
+}
+
+fn end_run(
+    mut deaths: EventReader<PlayerDied>,
+    mut game_over: EventWriter<GameOverEvent>,
+    score: Res<Score>,
+) {
+    for death in deaths.read() {
+        game_over.send(GameOverEvent {
+            score: score.value,
+            cause: death.cause,
+        });
+    }
+}
+
+fn combo_timeout(time: Res<Time>, mut combo: ResMut<Combo>) {
+    if combo.hits == 0 {
+        return;
+    }
+
+    combo
+        .timer
+        .tick(Duration::from_secs_f32(time.delta_seconds()));
+
+    if combo.timer.finished() {
+        combo.reset();
+    }
+}
+
+fn animate_score_popups(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut popup_query: Query<(Entity, &mut Transform, &mut Text, &mut ScorePopup)>,
+) {
+    for (entity, mut transform, mut text, mut popup) in popup_query.iter_mut() {
+        popup.lifetime.tick(time.delta());
+
+        if popup.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation.y += SCORE_POPUP_RISE_SPEED * time.delta_seconds();
+        for section in text.sections.iter_mut() {
+            section.style.color.set_a(popup.lifetime.percent_left());
+        }
+    }
+}
+
+fn reset_score(
+    mut commands: Commands,
+    mut score: ResMut<Score>,
+    mut combo: ResMut<Combo>,
+    popup_query: Query<Entity, With<ScorePopup>>,
+    config: Res<GameConfig>,
+) {
+    *score = Score::default();
+    *combo = Combo::new(config.combo_timeout);
+
+    for entity in popup_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> GameConfig {
+        GameConfig {
+            combo_hits_per_multiplier: 2,
+            combo_max_multiplier: 3,
+            combo_multi_kill_bonus: 5,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn multiplier_rises_with_hits_up_to_the_maximum() {
+        let config = config();
+        let mut combo = Combo::new(config.combo_timeout);
+
+        let points: Vec<_> = (1..=8).map(|shot| combo.hit(shot, 1, &config)).collect();
+
+        assert_eq!(points, [1, 2, 2, 3, 3, 3, 3, 3]);
+    }
+
+    #[test]
+    fn multi_kills_earn_a_bonus_per_extra_asteroid() {
+        let config = config();
+        let mut combo = Combo::new(config.combo_timeout);
+
+        assert_eq!(combo.hit(1, 1, &config), 1);
+        assert_eq!(combo.hit(1, 1, &config), 2 + 5);
+        assert_eq!(combo.hit(1, 1, &config), 2 + 10);
+        assert_eq!(combo.hit(2, 1, &config), 3);
+    }
+
+    #[test]
+    fn miss_breaks_the_combo() {
+        let config = config();
+        let mut combo = Combo::new(config.combo_timeout);
+
+        combo.hit(1, 1, &config);
+        combo.hit(2, 1, &config);
+        combo.miss(3);
+
+        assert_eq!(combo.hits, 0);
+        assert_eq!(combo.multiplier(&config), 1);
+    }
+
+    #[test]
+    fn miss_of_a_shot_that_hit_keeps_the_combo() {
+        let config = config();
+        let mut combo = Combo::new(config.combo_timeout);
+
+        combo.hit(1, 1, &config);
+        combo.hit(2, 1, &config);
+        combo.miss(2);
+
+        assert_eq!(combo.hits, 2);
+    }
+
+    #[test]
+    fn miss_of_an_older_shot_that_hit_keeps_the_combo() {
+        let config = config();
+        let mut combo = Combo::new(config.combo_timeout);
+
+        // The side bullets of a multishot expire long after the next shots.
+        for shot in 1..=10 {
+            combo.hit(shot, 1, &config);
+            if shot > 4 {
+                combo.miss(shot - 4);
+                combo.miss(shot - 4);
+            }
+        }
+
+        assert_eq!(combo.hits, 10);
+        assert_eq!(combo.multiplier(&config), 3);
+    }
+
+    #[test]
+    fn miss_of_a_forgotten_shot_breaks_the_combo() {
+        let config = config();
+        let mut combo = Combo::new(config.combo_timeout);
+
+        combo.hit(1, 1, &config);
+        combo.hit(2, 1, &config);
+        combo.retain_shots(|shot| shot == 2);
+        combo.miss(1);
+
+        assert_eq!(combo.hits, 0);
+    }
+}
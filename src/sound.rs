@@ -0,0 +1,106 @@
+use bevy::{
+    audio::{PitchBundle, Volume},
+    prelude::*,
+};
+use std::time::Duration;
+
+use crate::{
+    asteroid::AsteroidSize,
+    events::{AsteroidDestroyed, GameOverEvent, PlayerHit, ShotFired},
+};
+
+const SOUND_VOLUME: f32 = 0.2;
+
+/// Tones played for the gameplay events, generated so the game ships without
+/// audio files.
+#[derive(Resource)]
+pub struct SoundEffects {
+    pub shot: Handle<Pitch>,
+    pub large_explosion: Handle<Pitch>,
+    pub medium_explosion: Handle<Pitch>,
+    pub small_explosion: Handle<Pitch>,
+    pub shield_hit: Handle<Pitch>,
+    pub ship_hit: Handle<Pitch>,
+    pub game_over: Handle<Pitch>,
+}
+
+impl SoundEffects {
+    fn explosion(&self, size: AsteroidSize) -> &Handle<Pitch> {
+        match size {
+            AsteroidSize::Large => &self.large_explosion,
+            AsteroidSize::Medium => &self.medium_explosion,
+            AsteroidSize::Small => &self.small_explosion,
+        }
+    }
+}
+
+impl FromWorld for SoundEffects {
+    fn from_world(world: &mut World) -> Self {
+        let mut pitches = world.resource_mut::<Assets<Pitch>>();
+        let mut tone =
+            |frequency, millis| pitches.add(Pitch::new(frequency, Duration::from_millis(millis)));
+
+        Self {
+            shot: tone(880.0, 40),
+            large_explosion: tone(110.0, 150),
+            medium_explosion: tone(165.0, 120),
+            small_explosion: tone(220.0, 90),
+            shield_hit: tone(330.0, 150),
+            ship_hit: tone(80.0, 300),
+            game_over: tone(55.0, 800),
+        }
+    }
+}
+
+/// Plays a sound for the shots, destroyed asteroids, hits and the end of a
+/// run.
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SoundEffects>()
+            .add_systems(Update, play_sounds);
+    }
+}
+
+fn play_sound(commands: &mut Commands, sound: &Handle<Pitch>) {
+    commands.spawn(PitchBundle {
+        source: sound.clone(),
+        settings: PlaybackSettings::DESPAWN.with_volume(Volume::new_relative(SOUND_VOLUME)),
+    });
+}
+
+fn play_sounds(
+    mut commands: Commands,
+    mut fired: EventReader<ShotFired>,
+    mut destroyed: EventReader<AsteroidDestroyed>,
+    mut hits: EventReader<PlayerHit>,
+    mut game_over: EventReader<GameOverEvent>,
+    sounds: Res<SoundEffects>,
+) {
+    if fired.read().count() > 0 {
+        play_sound(&mut commands, &sounds.shot);
+    }
+
+    // A bomb destroys everything at once, one explosion is loud enough.
+    let largest = destroyed
+        .read()
+        .map(|event| event.size)
+        .min_by_key(|size| size.score());
+    if let Some(size) = largest {
+        play_sound(&mut commands, sounds.explosion(size));
+    }
+
+    for hit in hits.read() {
+        let sound = if hit.shielded {
+            &sounds.shield_hit
+        } else {
+            &sounds.ship_hit
+        };
+        play_sound(&mut commands, sound);
+    }
+
+    if game_over.read().count() > 0 {
+        play_sound(&mut commands, &sounds.game_over);
+    }
+}
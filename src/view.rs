@@ -0,0 +1,51 @@
+use bevy::{prelude::*, render::camera::ScalingMode, window::WindowResized};
+
+use crate::{WINDOW_HEIGHT, WINDOW_WIDTH};
+
+const LETTERBOX_SIZE: f32 = 10000.0;
+const LETTERBOX_Z: f32 = 100.0;
+
+/// Keeps the whole `WINDOW_WIDTH` x `WINDOW_HEIGHT` play field visible at any
+/// window size, scaled to fit and with black bars covering the rest.
+pub struct ViewPlugin;
+
+impl Plugin for ViewPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, (setup_camera, spawn_letterbox))
+            .add_systems(Update, scale_ui);
+    }
+}
+
+fn setup_camera(mut commands: Commands) {
+    let mut camera = Camera2dBundle::default();
+    camera.projection.scaling_mode = ScalingMode::AutoMin {
+        min_width: WINDOW_WIDTH,
+        min_height: WINDOW_HEIGHT,
+    };
+
+    commands.spawn(camera);
+}
+
+fn spawn_letterbox(mut commands: Commands) {
+    let offset = Vec2::new(WINDOW_WIDTH, WINDOW_HEIGHT) / 2.0 + LETTERBOX_SIZE / 2.0;
+
+    for direction in [Vec2::X, Vec2::NEG_X, Vec2::Y, Vec2::NEG_Y] {
+        commands.spawn(SpriteBundle {
+            sprite: Sprite {
+                color: Color::BLACK,
+                custom_size: Some(Vec2::splat(LETTERBOX_SIZE)),
+                ..default()
+            },
+            transform: Transform::from_translation((direction * offset).extend(LETTERBOX_Z)),
+            ..default()
+        });
+    }
+}
+
+/// Scales the menus and HUD along with the play field.
+fn scale_ui(mut resized: EventReader<WindowResized>, mut ui_scale: ResMut<UiScale>) {
+    for event in resized.read() {
+        let scale = (event.width / WINDOW_WIDTH).min(event.height / WINDOW_HEIGHT);
+        ui_scale.0 = scale as f64;
+    }
+}